@@ -5,17 +5,36 @@ use quote::quote;
 use syn::parse_macro_input;
 
 use self::pmbus::constants::CommandConstants;
+use self::pmbus::descriptors::CommandDescriptors;
 use self::pmbus::table::CommandsTable;
-use self::pmbus::trait_impl::PmBusTraitItem;
+use self::pmbus::trait_impl::{Flavor, PmBusTraitItem};
 
 #[proc_macro]
 pub fn impl_commands(input: TokenStream1) -> TokenStream1 {
     let table: CommandsTable = parse_macro_input!(input);
     let constants = CommandConstants::from(&table).0;
-    let pmbus_trait = PmBusTraitItem::from(table).0;
+    let descriptors = CommandDescriptors::from(&table);
+    let (pmbus_trait, blocking_pmbus_trait) = match (
+        PmBusTraitItem::from_table(&table, Flavor::Async),
+        PmBusTraitItem::from_table(&table, Flavor::Blocking),
+    ) {
+        (Ok(pmbus_trait), Ok(blocking_pmbus_trait)) => (pmbus_trait.0, blocking_pmbus_trait.0),
+        (Err(error), _) | (_, Err(error)) => return error.to_compile_error().into(),
+    };
     quote! {
         #(#constants)*
+        #descriptors
         #pmbus_trait
+
+        /// Blocking counterpart of [`PmBus`], generated from the same command table.
+        pub mod blocking {
+            use super::*;
+            use crate::smbus::blocking::SmBus;
+
+            pub use super::ext::blocking::PmBusExt;
+
+            #blocking_pmbus_trait
+        }
     }
     .into()
 }
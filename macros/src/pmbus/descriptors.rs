@@ -0,0 +1,72 @@
+use quote::{quote, ToTokens};
+use syn::{parse_quote, Expr};
+
+use super::table::{CommandByteCount, CommandIdent, CommandRead, CommandWrite, CommandsTable};
+
+/// A `CommandInfo` expression for every named command in the table, describing it at runtime.
+pub struct CommandDescriptors(pub Vec<Expr>);
+
+impl From<&CommandsTable> for CommandDescriptors {
+    fn from(table: &CommandsTable) -> Self {
+        Self(
+            table
+                .0
+                .iter()
+                .filter_map(|entry| {
+                    let CommandIdent::Verbatim(ident) = &entry.ident else {
+                        return None;
+                    };
+                    let byte = &entry.byte;
+                    let name = ident.to_string();
+                    let write = match &entry.write_kind {
+                        CommandWrite::Send(_) => quote!(Some(WriteKind::Send)),
+                        CommandWrite::Write(..)
+                        | CommandWrite::Vout(..)
+                        | CommandWrite::Numeric(..) => {
+                            quote!(Some(WriteKind::Write))
+                        }
+                        CommandWrite::Undefined(_) | CommandWrite::Unimplemented(_) => quote!(None),
+                    };
+                    let read = match &entry.read_kind {
+                        CommandRead::Read(..)
+                        | CommandRead::Vout(..)
+                        | CommandRead::Numeric(..) => {
+                            quote!(Some(ReadKind::Read))
+                        }
+                        CommandRead::Call(..) => quote!(Some(ReadKind::Call)),
+                        CommandRead::Undefined(_) | CommandRead::Unimplemented(_) => quote!(None),
+                    };
+                    let size = match &entry.byte_count {
+                        CommandByteCount::Count(_, byte_count) => {
+                            let byte_count = *byte_count as usize;
+                            quote!(Some(#byte_count))
+                        }
+                        CommandByteCount::Undefined(_) | CommandByteCount::Unimplemented(_) => {
+                            quote!(None)
+                        }
+                    };
+                    Some(parse_quote! {
+                        CommandInfo {
+                            code: #byte,
+                            name: #name,
+                            write: #write,
+                            read: #read,
+                            size: #size,
+                        }
+                    })
+                })
+                .collect(),
+        )
+    }
+}
+
+impl ToTokens for CommandDescriptors {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let descriptors = &self.0;
+        quote! {
+            /// Every command defined in the table, in order of command code.
+            pub const COMMANDS: &[CommandInfo] = &[#(#descriptors),*];
+        }
+        .to_tokens(tokens)
+    }
+}
@@ -1,3 +1,4 @@
 pub mod constants;
+pub mod descriptors;
 pub mod table;
 pub mod trait_impl;
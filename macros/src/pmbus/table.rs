@@ -3,13 +3,15 @@ use quote::{quote, quote_spanned, ToTokens};
 use syn::parse::Parse;
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{Ident, LitInt, Token, Type};
+use syn::{Ident, Lit, LitInt, Token, Type};
 
 mod kw {
     syn::custom_keyword!(write);
     syn::custom_keyword!(send);
     syn::custom_keyword!(read);
     syn::custom_keyword!(call);
+    syn::custom_keyword!(vout);
+    syn::custom_keyword!(numeric);
 }
 
 pub enum CommandIdent {
@@ -38,13 +40,61 @@ impl ToTokens for CommandIdent {
     }
 }
 
+/// A physical quantity, in the numeric format of the device (LINEAR11 or DIRECT).
+///
+/// The value on the bus is optionally multiplied by a scale to give the quantity,
+/// such as `numeric Hertz * 1000` for a value in kilohertz.
+pub struct NumericType {
+    pub numeric: kw::numeric,
+    pub ty: Box<Type>,
+    pub scale: Option<(Token![*], Lit)>,
+}
+
+impl NumericType {
+    /// The scale as a number, `1.0` if there is none.
+    pub fn scale(&self) -> syn::Result<f64> {
+        match &self.scale {
+            None => Ok(1.0),
+            Some((_, Lit::Int(lit))) => lit.base10_parse(),
+            Some((_, Lit::Float(lit))) => lit.base10_parse(),
+            Some((_, lit)) => Err(syn::Error::new(lit.span(), "expected a numeric scale")),
+        }
+    }
+}
+
+impl Parse for NumericType {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let (numeric, ty) = (input.parse()?, input.parse()?);
+        let scale = if input.peek(Token![*]) {
+            Some((input.parse()?, input.parse()?))
+        } else {
+            None
+        };
+        Ok(Self { numeric, ty, scale })
+    }
+}
+
+impl ToTokens for NumericType {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let Self { numeric, ty, scale } = self;
+        quote!(#numeric #ty).to_tokens(tokens);
+        if let Some((star, scale)) = scale {
+            quote!(#star #scale).to_tokens(tokens);
+        }
+    }
+}
+
 pub enum CommandWrite {
     // UNDEFINED / RESERVED
     Undefined(Token![_]),
     // UNIMPLEMENTED / MANUFACTURER
     Unimplemented(Token![!]),
     // WRITE TYPE
-    Write(kw::write, Token![:], Type),
+    Write(kw::write, Token![:], Box<Type>),
+    // OUTPUT VOLTAGE, FORMAT SELECTED BY VOUT_MODE
+    Vout(kw::write, Token![:], kw::vout),
+    // PHYSICAL QUANTITY, FORMAT SELECTED BY THE DEVICE
+    Numeric(kw::write, Token![:], NumericType),
     // SEND COMMAND BIT
     Send(kw::send),
 }
@@ -59,8 +109,17 @@ impl Parse for CommandWrite {
             // UNIMPLEMENTED / MANUFACTURER
             input.parse().map(Self::Unimplemented)
         } else if look.peek(kw::write) {
-            // WRITE TYPE
-            Ok(Self::Write(input.parse()?, input.parse()?, input.parse()?))
+            let (write, colon) = (input.parse()?, input.parse()?);
+            if input.peek(kw::vout) {
+                // OUTPUT VOLTAGE
+                Ok(Self::Vout(write, colon, input.parse()?))
+            } else if input.peek(kw::numeric) {
+                // PHYSICAL QUANTITY
+                Ok(Self::Numeric(write, colon, input.parse()?))
+            } else {
+                // WRITE TYPE
+                Ok(Self::Write(write, colon, input.parse()?))
+            }
         } else if look.peek(kw::send) {
             // SEND COMMAND BIT
             input.parse().map(Self::Send)
@@ -76,6 +135,10 @@ impl ToTokens for CommandWrite {
             Self::Undefined(underscore) => underscore.to_tokens(tokens),
             Self::Unimplemented(never) => never.to_tokens(tokens),
             Self::Write(write, colon, ty) => quote!(#write #colon #ty).to_tokens(tokens),
+            Self::Vout(write, colon, vout) => quote!(#write #colon #vout).to_tokens(tokens),
+            Self::Numeric(write, colon, numeric) => {
+                quote!(#write #colon #numeric).to_tokens(tokens)
+            }
             Self::Send(send) => send.to_tokens(tokens),
         }
     }
@@ -88,8 +151,12 @@ pub enum CommandRead {
     Unimplemented(Token![!]),
     // READ TYPE
     Read(kw::read, Token![:], Type),
-    // PROCEDURE CALL TYPE
-    Call(kw::call, Token![:], Type),
+    // OUTPUT VOLTAGE, FORMAT SELECTED BY VOUT_MODE
+    Vout(kw::read, Token![:], kw::vout),
+    // PHYSICAL QUANTITY, FORMAT SELECTED BY THE DEVICE
+    Numeric(kw::read, Token![:], NumericType),
+    // PROCEDURE CALL TYPE, OPTIONALLY WITH A RETURN TYPE
+    Call(kw::call, Token![:], Type, Option<(Token![->], Box<Type>)>),
 }
 
 impl ToTokens for CommandRead {
@@ -98,7 +165,16 @@ impl ToTokens for CommandRead {
             CommandRead::Undefined(underscore) => underscore.to_tokens(tokens),
             CommandRead::Unimplemented(never) => never.to_tokens(tokens),
             CommandRead::Read(read, colon, ty) => quote!(#read #colon #ty).to_tokens(tokens),
-            CommandRead::Call(call, colon, ty) => quote!(#call #colon #ty).to_tokens(tokens),
+            CommandRead::Vout(read, colon, vout) => quote!(#read #colon #vout).to_tokens(tokens),
+            CommandRead::Numeric(read, colon, numeric) => {
+                quote!(#read #colon #numeric).to_tokens(tokens)
+            }
+            CommandRead::Call(call, colon, ty, output) => {
+                quote!(#call #colon #ty).to_tokens(tokens);
+                if let Some((arrow, output)) = output {
+                    quote!(#arrow #output).to_tokens(tokens);
+                }
+            }
         }
     }
 }
@@ -113,11 +189,26 @@ impl Parse for CommandRead {
             // UNIMPLEMENTED / MANUFACTURER
             input.parse().map(Self::Unimplemented)
         } else if look.peek(kw::read) {
-            // READ TYPE
-            Ok(Self::Read(input.parse()?, input.parse()?, input.parse()?))
+            let (read, colon) = (input.parse()?, input.parse()?);
+            if input.peek(kw::vout) {
+                // OUTPUT VOLTAGE
+                Ok(Self::Vout(read, colon, input.parse()?))
+            } else if input.peek(kw::numeric) {
+                // PHYSICAL QUANTITY
+                Ok(Self::Numeric(read, colon, input.parse()?))
+            } else {
+                // READ TYPE
+                Ok(Self::Read(read, colon, input.parse()?))
+            }
         } else if look.peek(kw::call) {
             // PROCEDURE CALL TYPE
-            Ok(Self::Call(input.parse()?, input.parse()?, input.parse()?))
+            let (call, colon, ty) = (input.parse()?, input.parse()?, input.parse()?);
+            let output = if input.peek(Token![->]) {
+                Some((input.parse()?, input.parse()?))
+            } else {
+                None
+            };
+            Ok(Self::Call(call, colon, ty, output))
         } else {
             Err(look.error())
         }
@@ -1,34 +1,114 @@
 use heck::ToSnakeCase;
-use quote::{format_ident, ToTokens};
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, ToTokens};
 use syn::spanned::Spanned;
-use syn::{parse_quote, parse_quote_spanned, Ident, ItemFn, ItemTrait, Type};
+use syn::{parse_quote, parse_quote_spanned, Ident, ItemFn, ItemTrait, Token, Type};
 
 use super::table::{
     CommandByteCount, CommandEntry, CommandIdent, CommandRead, CommandWrite, CommandsTable,
+    NumericType,
 };
 
+/// Whether a trait is generated with asynchronous methods, or as its blocking counterpart.
+#[derive(Clone, Copy)]
+pub enum Flavor {
+    Async,
+    Blocking,
+}
+
+impl Flavor {
+    /// The `async` keyword, or nothing.
+    fn asyncness(self) -> Option<Token![async]> {
+        match self {
+            Self::Async => Some(Default::default()),
+            Self::Blocking => None,
+        }
+    }
+
+    /// The `.await` postfix, or nothing.
+    fn dot_await(self) -> TokenStream {
+        match self {
+            Self::Async => quote!(.await),
+            Self::Blocking => quote!(),
+        }
+    }
+}
+
 pub struct PmBusTraitItem(pub ItemTrait);
 
-impl From<CommandsTable> for PmBusTraitItem {
-    fn from(table: CommandsTable) -> Self {
+impl PmBusTraitItem {
+    pub fn from_table(table: &CommandsTable, flavor: Flavor) -> syn::Result<Self> {
         // TODO: Stop mapping to the inner value. I'm leaving this alone for now because
         // I expect it to change significantly once the structure of read and write data is better defined.
         let write_command_fns = table
             .0
             .iter()
-            .filter_map(|entry| WriteCommandFn::from_table_entry(entry).map(|write| write.0));
+            .filter_map(|entry| {
+                WriteCommandFn::from_table_entry(entry, flavor)
+                    .transpose()
+                    .map(|write| write.map(|write| write.0))
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
         let read_command_fns = table
             .0
             .iter()
-            .filter_map(|entry| ReadCommandFn::from_table_entry(entry).map(|write| write.0));
+            .filter_map(|entry| {
+                ReadCommandFn::from_table_entry(entry, flavor)
+                    .transpose()
+                    .map(|read| read.map(|read| read.0))
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        let async_trait_attr = match flavor {
+            Flavor::Async => Some(quote!(#[::async_trait::async_trait(?Send)])),
+            Flavor::Blocking => None,
+        };
+
+        Ok(Self(parse_quote! {
+            #async_trait_attr
+            pub trait PmBus<A: SmBusAddress = ::embedded_hal::i2c::SevenBitAddress>: SmBus<A> {
+                /// State kept between commands, such as the VOUT_MODE of each page.
+                ///
+                /// Returns `None` by default, in which case state is read back from the device every time it is needed.
+                fn cache(&mut self) -> Option<&mut Cache<A>> {
+                    None
+                }
+
+                /// DIRECT format coefficients of `command` known in advance, such as from a datasheet,
+                /// for reading (`read == true`) or writing it.
+                ///
+                /// Returns `None` by default. This is needed for devices which do not support COEFFICIENTS.
+                fn fixed_coefficients(&self, address: A, command: u8, read: bool) -> Option<Coefficients> {
+                    let _ = (address, command, read);
+                    None
+                }
+
+                /// The VID code table selected by the VID `code_type` of VOUT_MODE, as given by the datasheet of the device.
+                ///
+                /// Returns `None` by default, in which case VID data cannot be converted.
+                fn vid_table(&self, address: A, code_type: u8) -> Option<VidCode> {
+                    let _ = (address, code_type);
+                    None
+                }
+
+                /// The numeric format of `command`, other than output voltages.
+                ///
+                /// Returns [`NumericFormat::Direct`] by default if there are [`fixed_coefficients`](PmBus::fixed_coefficients)
+                /// for reading or writing `command`, and [`NumericFormat::Linear11`] otherwise.
+                /// Devices using DIRECT with coefficients read by COEFFICIENTS should override this.
+                fn numeric_format(&self, address: A, command: u8) -> NumericFormat {
+                    let fixed = |read| <Self as PmBus<A>>::fixed_coefficients(self, address, command, read);
+                    if fixed(true).or_else(|| fixed(false)).is_some() {
+                        NumericFormat::Direct
+                    } else {
+                        NumericFormat::Linear11
+                    }
+                }
 
-        Self(parse_quote! {
-            #[::async_trait::async_trait(?Send)]
-            pub trait PmBus<A: ::embedded_hal::i2c::AddressMode = ::embedded_hal::i2c::SevenBitAddress>: SmBus<A> {
                 #(#write_command_fns)*
                 #(#read_command_fns)*
             }
-        })
+        }))
     }
 }
 
@@ -37,7 +117,9 @@ impl From<CommandsTable> for PmBusTraitItem {
 pub struct WriteCommandFn(pub ItemFn);
 
 impl WriteCommandFn {
-    pub fn from_table_entry(entry: &CommandEntry) -> Option<Self> {
+    pub fn from_table_entry(entry: &CommandEntry, flavor: Flavor) -> syn::Result<Option<Self>> {
+        let (asyncness, dot_await) = (flavor.asyncness(), flavor.dot_await());
+
         let gen_send_fn = |command: &Ident| -> ItemFn {
             let write_fn_ident = format_ident!(
                 "write_{base_ident}",
@@ -45,25 +127,90 @@ impl WriteCommandFn {
             );
             parse_quote_spanned! {
                 entry.span() =>
-                async fn #write_fn_ident(&mut self, address: A) -> ::std::result::Result<(), <Self as ::embedded_hal::i2c::ErrorType>::Error> {
-                    <Self as SmBus<A>>::send_byte(self, address, #command).await
+                #asyncness fn #write_fn_ident(&mut self, address: A) -> ::std::result::Result<(), PmBusError<<Self as ::embedded_hal::i2c::ErrorType>::Error>> {
+                    <Self as SmBus<A>>::send_byte(self, address, #command) #dot_await ?;
+                    if let Some(cache) = <Self as PmBus<A>>::cache(self) {
+                        cache.written(address, #command, &[]);
+                    }
+                    Ok(())
+                }
+            }
+        };
+
+        // The data is converted to `wire_ty`, the type taken by `write_op`, and then viewed as the bytes written by `wire_bytes`.
+        // Those bytes are given to the cache. The conversion uses `TryFrom`, so that types can refuse invalid values.
+        let gen_write_fn = |write_op: Ident,
+                            command: &Ident,
+                            ty: &Type,
+                            wire_ty: TokenStream,
+                            wire_bytes: TokenStream|
+         -> ItemFn {
+            let send_fn_ident = format_ident!(
+                "send_{base_ident}",
+                base_ident = Ident::new(&command.to_string().to_snake_case(), command.span())
+            );
+            parse_quote_spanned! {
+                entry.span() =>
+                #[allow(clippy::useless_conversion, clippy::unnecessary_fallible_conversions)]
+                #asyncness fn #send_fn_ident(&mut self, address: A, data: #ty) -> ::std::result::Result<(), PmBusError<<Self as ::embedded_hal::i2c::ErrorType>::Error>> {
+                    let data = <#wire_ty>::try_from(data)?;
+                    <Self as SmBus<A>>::#write_op(self, address, #command, data) #dot_await ?;
+                    if let Some(cache) = <Self as PmBus<A>>::cache(self) {
+                        cache.written(address, #command, #wire_bytes);
+                    }
+                    Ok(())
                 }
             }
         };
 
-        let gen_write_fn = |write_op: Ident, command: &Ident, ty: &Type| -> ItemFn {
+        // Output voltages are encoded in the format selected by VOUT_MODE, and written as a word.
+        let gen_vout_write_fn = |command: &Ident| -> ItemFn {
             let send_fn_ident = format_ident!(
                 "send_{base_ident}",
                 base_ident = Ident::new(&command.to_string().to_snake_case(), command.span())
             );
             parse_quote_spanned! {
                 entry.span() =>
-                async fn #send_fn_ident(&mut self, address: A, data: #ty) -> ::std::result::Result<(), <Self as ::embedded_hal::i2c::ErrorType>::Error> {
-                    <Self as SmBus<A>>::#write_op(self, address, #command, data).await
+                #asyncness fn #send_fn_ident(&mut self, address: A, volts: Volts) -> ::std::result::Result<(), PmBusError<<Self as ::embedded_hal::i2c::ErrorType>::Error>> {
+                    let data = <Self as PmBusExt<A>>::encode_vout(self, address, #command, volts.0) #dot_await ?;
+                    <Self as SmBus<A>>::write_word(self, address, #command, data) #dot_await ?;
+                    if let Some(cache) = <Self as PmBus<A>>::cache(self) {
+                        cache.written(address, #command, &data.to_le_bytes());
+                    }
+                    Ok(())
                 }
             }
         };
 
+        // Physical quantities are encoded in the numeric format of the device, and written as a word.
+        let gen_numeric_write_fn = |command: &Ident,
+                                    numeric: &NumericType|
+         -> syn::Result<ItemFn> {
+            let send_fn_ident = format_ident!(
+                "send_{base_ident}",
+                base_ident = Ident::new(&command.to_string().to_snake_case(), command.span())
+            );
+            let ty = &numeric.ty;
+            let value = match numeric.scale()? {
+                1.0 => quote!(::core::convert::Into::<f64>::into(value)),
+                scale => {
+                    let scale = proc_macro2::Literal::f64_suffixed(scale);
+                    quote!(::core::convert::Into::<f64>::into(value) / #scale)
+                }
+            };
+            Ok(parse_quote_spanned! {
+                entry.span() =>
+                #asyncness fn #send_fn_ident(&mut self, address: A, value: #ty) -> ::std::result::Result<(), PmBusError<<Self as ::embedded_hal::i2c::ErrorType>::Error>> {
+                    let data = <Self as PmBusExt<A>>::encode_numeric(self, address, #command, #value) #dot_await ?;
+                    <Self as SmBus<A>>::write_word(self, address, #command, data) #dot_await ?;
+                    if let Some(cache) = <Self as PmBus<A>>::cache(self) {
+                        cache.written(address, #command, &data.to_le_bytes());
+                    }
+                    Ok(())
+                }
+            })
+        };
+
         match entry {
             // TRIVIAL VALIDATION PATTERN
             // This needs to be somewhere else, this constructor for `WriteCommandFn`
@@ -81,7 +228,7 @@ impl WriteCommandFn {
             //     read_kind: CommandRead::Unimplemented(_),
             //     byte_count: CommandByteCount::Unimplemented(_),
             //     ..
-            // } => None,
+            // } => Ok(None),
             // Discard entries which do not have a write operation.
             CommandEntry {
                 write_kind: CommandWrite::Undefined(_),
@@ -90,41 +237,73 @@ impl WriteCommandFn {
             | CommandEntry {
                 write_kind: CommandWrite::Unimplemented(_),
                 ..
-            } => None,
+            } => Ok(None),
             // Data length is one byte, the operation is `write_byte`.
             CommandEntry {
                 ident: CommandIdent::Verbatim(command),
                 write_kind: CommandWrite::Write(_, _, ty),
                 byte_count: CommandByteCount::Count(byte_count_span, 1),
                 ..
-            } => Some(Self(gen_write_fn(
+            } => Ok(Some(Self(gen_write_fn(
                 Ident::new("write_byte", *byte_count_span),
                 command,
                 ty,
-            ))),
+                quote!(u8),
+                quote!(&[data]),
+            )))),
             // Data length is two bytes, the operation is `write_word`.
             CommandEntry {
                 ident: CommandIdent::Verbatim(command),
                 write_kind: CommandWrite::Write(_, _, ty),
                 byte_count: CommandByteCount::Count(byte_count_span, 2),
                 ..
-            } => Some(Self(gen_write_fn(
+            } => Ok(Some(Self(gen_write_fn(
                 Ident::new("write_word", *byte_count_span),
                 command,
                 ty,
-            ))),
-            // The data size is known, but it is not a byte or a word.
+                quote!(u16),
+                quote!(&data.to_le_bytes()),
+            )))),
+            // Data length is four bytes, the operation is `write_32`.
+            CommandEntry {
+                ident: CommandIdent::Verbatim(command),
+                write_kind: CommandWrite::Write(_, _, ty),
+                byte_count: CommandByteCount::Count(byte_count_span, 4),
+                ..
+            } => Ok(Some(Self(gen_write_fn(
+                Ident::new("write_32", *byte_count_span),
+                command,
+                ty,
+                quote!(u32),
+                quote!(&data.to_le_bytes()),
+            )))),
+            // Data length is eight bytes, the operation is `write_64`.
+            CommandEntry {
+                ident: CommandIdent::Verbatim(command),
+                write_kind: CommandWrite::Write(_, _, ty),
+                byte_count: CommandByteCount::Count(byte_count_span, 8),
+                ..
+            } => Ok(Some(Self(gen_write_fn(
+                Ident::new("write_64", *byte_count_span),
+                command,
+                ty,
+                quote!(u64),
+                quote!(&data.to_le_bytes()),
+            )))),
+            // The data size is known, but it is not a byte, a word, or a 32/64-bit value.
             // The operation is `block_write`.
             CommandEntry {
                 ident: CommandIdent::Verbatim(command),
                 write_kind: CommandWrite::Write(_, _, ty),
                 byte_count: CommandByteCount::Count(byte_count_span, _),
                 ..
-            } => Some(Self(gen_write_fn(
+            } => Ok(Some(Self(gen_write_fn(
                 Ident::new("block_write", *byte_count_span),
                 command,
                 ty,
-            ))),
+                quote!(&[u8]),
+                quote!(data),
+            )))),
             // Write kind is known, but data length undefined. This means we treat the data as a variable-sized block.
             // The operation is `block_write`.
             CommandEntry {
@@ -132,11 +311,27 @@ impl WriteCommandFn {
                 write_kind: CommandWrite::Write(_, _, ty),
                 byte_count: CommandByteCount::Undefined(underscore),
                 ..
-            } => Some(Self(gen_write_fn(
+            } => Ok(Some(Self(gen_write_fn(
                 Ident::new("block_write", underscore.span()),
                 command,
                 ty,
-            ))),
+                quote!(&[u8]),
+                quote!(data),
+            )))),
+            // Output voltages are always a word.
+            CommandEntry {
+                ident: CommandIdent::Verbatim(command),
+                write_kind: CommandWrite::Vout(..),
+                byte_count: CommandByteCount::Count(_, 2),
+                ..
+            } => Ok(Some(Self(gen_vout_write_fn(command)))),
+            // Physical quantities are always a word.
+            CommandEntry {
+                ident: CommandIdent::Verbatim(command),
+                write_kind: CommandWrite::Numeric(_, _, numeric),
+                byte_count: CommandByteCount::Count(_, 2),
+                ..
+            } => Ok(Some(Self(gen_numeric_write_fn(command, numeric)?))),
             // The `write_kind` is `Send` and the `byte_count` is `0`, so the operation is `send_byte`.
             // Using `send` with nonzero data size is expressly prohibited.
             CommandEntry {
@@ -144,12 +339,15 @@ impl WriteCommandFn {
                 write_kind: CommandWrite::Send(_),
                 byte_count: CommandByteCount::Count(_, 0),
                 ..
-            } => Some(Self(gen_send_fn(command))),
+            } => Ok(Some(Self(gen_send_fn(command)))),
             // TODO: See comment TEST VALIDATION PATTERN.
-            _ => {
-                println!("{}", entry.to_token_stream());
-                unimplemented!("edge cases and invalid entries not yet handled")
-            }
+            _ => Err(syn::Error::new(
+                entry.span(),
+                format!(
+                    "unsupported write definition in command table entry `{}`",
+                    entry.to_token_stream()
+                ),
+            )),
         }
     }
 }
@@ -157,34 +355,138 @@ impl WriteCommandFn {
 pub struct ReadCommandFn(pub ItemFn);
 
 impl ReadCommandFn {
-    pub fn from_table_entry(entry: &CommandEntry) -> Option<Self> {
-        let gen_read_fn = |read_op: Ident, command: &Ident, ty: &Type| -> ItemFn {
+    pub fn from_table_entry(entry: &CommandEntry, flavor: Flavor) -> syn::Result<Option<Self>> {
+        let (asyncness, dot_await) = (flavor.asyncness(), flavor.dot_await());
+
+        // Any arguments to the `SmBus` operation after the command code are passed in `read_args`.
+        // The data is converted with `TryFrom`, so that types can reject reserved values.
+        let gen_read_fn = |read_op: Ident,
+                           command: &Ident,
+                           ty: &Type,
+                           read_args: TokenStream|
+         -> ItemFn {
+            let read_fn_ident = format_ident!(
+                "read_{base_ident}",
+                base_ident = Ident::new(&command.to_string().to_snake_case(), command.span())
+            );
+            parse_quote_spanned! {
+                entry.span() =>
+                #[allow(clippy::useless_conversion, clippy::unnecessary_fallible_conversions)]
+                #asyncness fn #read_fn_ident(&mut self, address: A) -> ::std::result::Result<#ty, PmBusError<<Self as ::embedded_hal::i2c::ErrorType>::Error>> {
+                    let data = <Self as SmBus<A>>::#read_op(self, address, #command #read_args) #dot_await ?;
+                    Ok(<#ty>::try_from(data)?)
+                }
+            }
+        };
+
+        // Blocks of a known size are converted from an array, so that the type can rely on the size.
+        let gen_exact_read_fn = |command: &Ident,
+                                 ty: &Type,
+                                 byte_count_span: Span,
+                                 byte_count: usize|
+         -> ItemFn {
             let read_fn_ident = format_ident!(
                 "read_{base_ident}",
                 base_ident = Ident::new(&command.to_string().to_snake_case(), command.span())
             );
+            let read_op = Ident::new("block_read_exact", byte_count_span);
             parse_quote_spanned! {
                 entry.span() =>
-                async fn #read_fn_ident(&mut self, address: A) -> ::std::result::Result<#ty, <Self as ::embedded_hal::i2c::ErrorType>::Error> {
-                    <Self as SmBus<A>>::#read_op(self, address, #command).await.map(Into::into)
+                #asyncness fn #read_fn_ident(&mut self, address: A) -> ::std::result::Result<#ty, PmBusError<<Self as ::embedded_hal::i2c::ErrorType>::Error>> {
+                    let block = <Self as SmBus<A>>::#read_op(self, address, #command, #byte_count) #dot_await ?;
+                    let block = <[u8; #byte_count]>::try_from(block).map_err(|block| PmBusError::ByteCountMismatch {
+                        expected: #byte_count,
+                        received: block.len(),
+                    })?;
+                    Ok(block.into())
                 }
             }
         };
 
+        // Output voltages are read as a word, and decoded in the format selected by VOUT_MODE.
+        let gen_vout_read_fn = |command: &Ident| -> ItemFn {
+            let read_fn_ident = format_ident!(
+                "read_{base_ident}",
+                base_ident = Ident::new(&command.to_string().to_snake_case(), command.span())
+            );
+            parse_quote_spanned! {
+                entry.span() =>
+                #asyncness fn #read_fn_ident(&mut self, address: A) -> ::std::result::Result<Volts, PmBusError<<Self as ::embedded_hal::i2c::ErrorType>::Error>> {
+                    let data = <Self as SmBus<A>>::read_word(self, address, #command) #dot_await ?;
+                    <Self as PmBusExt<A>>::decode_vout(self, address, #command, data) #dot_await .map(Volts)
+                }
+            }
+        };
+
+        // Physical quantities are read as a word, and decoded in the numeric format of the device.
+        let gen_numeric_read_fn = |command: &Ident, numeric: &NumericType| -> syn::Result<ItemFn> {
+            let read_fn_ident = format_ident!(
+                "read_{base_ident}",
+                base_ident = Ident::new(&command.to_string().to_snake_case(), command.span())
+            );
+            let ty = &numeric.ty;
+            let value = match numeric.scale()? {
+                1.0 => quote!(value),
+                scale => {
+                    let scale = proc_macro2::Literal::f64_suffixed(scale);
+                    quote!(value * #scale)
+                }
+            };
+            Ok(parse_quote_spanned! {
+                entry.span() =>
+                #asyncness fn #read_fn_ident(&mut self, address: A) -> ::std::result::Result<#ty, PmBusError<<Self as ::embedded_hal::i2c::ErrorType>::Error>> {
+                    let data = <Self as SmBus<A>>::read_word(self, address, #command) #dot_await ?;
+                    let value = <Self as PmBusExt<A>>::decode_numeric(self, address, #command, data) #dot_await ?;
+                    Ok(<#ty>::from(#value))
+                }
+            })
+        };
+
         // Currently all process calls are treated as "Block Read - Block Write Process Call" operations.
         // We will need to change this (or expand on it) as the write and return types become well-known.
         // Currently type is ignored.
-        let gen_proc_call_fn = |command: &Ident, _ty: &Type| -> ItemFn {
+        // Blocks read back with a known size are read exactly, with `block_process_call_exact`.
+        let gen_proc_call_fn = |command: &Ident, _ty: &Type, byte_count: Option<usize>| -> ItemFn {
             let call_fn_ident = format_ident!(
                 "call_{base_ident}",
                 base_ident = Ident::new(&command.to_string().to_snake_case(), command.span())
             );
+            let call = match byte_count {
+                Some(byte_count) => quote! {
+                    <Self as SmBus<A>>::block_process_call_exact(self, address, #command, write_block, #byte_count)
+                },
+                None => quote! {
+                    <Self as SmBus<A>>::block_process_call(self, address, #command, write_block)
+                },
+            };
             // TODO: The return value is fixed as a byte-vector.
             // Might be best to interpret the write type from another keyword in the write column.
             parse_quote_spanned! {
                 entry.span() =>
-                async fn #call_fn_ident(&mut self, address: A, write_block: &[u8]) -> ::std::result::Result<Vec<u8>, <Self as ::embedded_hal::i2c::ErrorType>::Error> {
-                    <Self as SmBus<A>>::block_process_call(self, address, #command, write_block).await
+                #asyncness fn #call_fn_ident(&mut self, address: A, write_block: &[u8]) -> ::std::result::Result<Vec<u8>, PmBusError<<Self as ::embedded_hal::i2c::ErrorType>::Error>> {
+                    #call #dot_await .map_err(Into::into)
+                }
+            }
+        };
+
+        let gen_typed_proc_call_fn = |command: &Ident,
+                                      ty: &Type,
+                                      output: &Type,
+                                      byte_count: usize|
+         -> ItemFn {
+            let call_fn_ident = format_ident!(
+                "call_{base_ident}",
+                base_ident = Ident::new(&command.to_string().to_snake_case(), command.span())
+            );
+            parse_quote_spanned! {
+                entry.span() =>
+                #asyncness fn #call_fn_ident(&mut self, address: A, data: #ty) -> ::std::result::Result<#output, PmBusError<<Self as ::embedded_hal::i2c::ErrorType>::Error>> {
+                    let block = <Self as SmBus<A>>::block_process_call_exact(self, address, #command, ::core::convert::AsRef::<[u8]>::as_ref(&data), #byte_count) #dot_await ?;
+                    let block = <[u8; #byte_count]>::try_from(block).map_err(|block| PmBusError::ByteCountMismatch {
+                        expected: #byte_count,
+                        received: block.len(),
+                    })?;
+                    Ok(block.into())
                 }
             }
         };
@@ -198,77 +500,147 @@ impl ReadCommandFn {
             | CommandEntry {
                 read_kind: CommandRead::Unimplemented(_),
                 ..
-            } => None,
+            } => Ok(None),
             // Data length is one byte, the operation is `read_byte`.
             CommandEntry {
                 ident: CommandIdent::Verbatim(command),
                 read_kind: CommandRead::Read(_, _, ty),
                 byte_count: CommandByteCount::Count(byte_count_span, 1),
                 ..
-            } => Some(Self(gen_read_fn(
+            } => Ok(Some(Self(gen_read_fn(
                 Ident::new("read_byte", *byte_count_span),
                 command,
                 ty,
-            ))),
+                quote!(),
+            )))),
             // Data length is one byte, the operation is `read_word`.
             CommandEntry {
                 ident: CommandIdent::Verbatim(command),
                 read_kind: CommandRead::Read(_, _, ty),
                 byte_count: CommandByteCount::Count(byte_count_span, 2),
                 ..
-            } => Some(Self(gen_read_fn(
+            } => Ok(Some(Self(gen_read_fn(
                 Ident::new("read_word", *byte_count_span),
                 command,
                 ty,
-            ))),
-            // The data size is known, but it is not a byte or a word.
-            // The operation is `block_write`.
+                quote!(),
+            )))),
+            // Data length is four bytes, the operation is `read_32`.
             CommandEntry {
                 ident: CommandIdent::Verbatim(command),
                 read_kind: CommandRead::Read(_, _, ty),
-                byte_count: CommandByteCount::Count(byte_count_span, _),
+                byte_count: CommandByteCount::Count(byte_count_span, 4),
                 ..
-            } => Some(Self(gen_read_fn(
-                Ident::new("block_read", *byte_count_span),
+            } => Ok(Some(Self(gen_read_fn(
+                Ident::new("read_32", *byte_count_span),
                 command,
                 ty,
-            ))),
-            // Write kind is known, but data length undefined. This means we treat the data as a variable-sized block.
-            // The operation is `block_write`.
+                quote!(),
+            )))),
+            // Data length is eight bytes, the operation is `read_64`.
+            CommandEntry {
+                ident: CommandIdent::Verbatim(command),
+                read_kind: CommandRead::Read(_, _, ty),
+                byte_count: CommandByteCount::Count(byte_count_span, 8),
+                ..
+            } => Ok(Some(Self(gen_read_fn(
+                Ident::new("read_64", *byte_count_span),
+                command,
+                ty,
+                quote!(),
+            )))),
+            // The data size is known, but it is not a byte, a word, or a 32/64-bit value.
+            // The operation is `block_read_exact`.
+            CommandEntry {
+                ident: CommandIdent::Verbatim(command),
+                read_kind: CommandRead::Read(_, _, ty),
+                byte_count: CommandByteCount::Count(byte_count_span, byte_count),
+                ..
+            } => Ok(Some(Self(gen_exact_read_fn(
+                command,
+                ty,
+                *byte_count_span,
+                *byte_count as usize,
+            )))),
+            // Read kind is known, but data length undefined. This means we treat the data as a variable-sized block.
+            // The operation is `block_read`.
             CommandEntry {
                 ident: CommandIdent::Verbatim(command),
                 read_kind: CommandRead::Read(_, _, ty),
                 byte_count: CommandByteCount::Undefined(underscore),
                 ..
-            } => Some(Self(gen_read_fn(
+            } => Ok(Some(Self(gen_read_fn(
                 Ident::new("block_read", underscore.span()),
                 command,
                 ty,
-            ))),
+                quote!(),
+            )))),
+            // Output voltages are always a word.
+            CommandEntry {
+                ident: CommandIdent::Verbatim(command),
+                read_kind: CommandRead::Vout(..),
+                byte_count: CommandByteCount::Count(_, 2),
+                ..
+            } => Ok(Some(Self(gen_vout_read_fn(command)))),
+            // Physical quantities are always a word.
+            CommandEntry {
+                ident: CommandIdent::Verbatim(command),
+                read_kind: CommandRead::Numeric(_, _, numeric),
+                byte_count: CommandByteCount::Count(_, 2),
+                ..
+            } => Ok(Some(Self(gen_numeric_read_fn(command, numeric)?))),
             // Process calls have many variations that need to be accounted for.
             // For example, the write data could be two bytes and the data read back is variable, or a fixed size.
             // The current `SmBus` trait just treats all process calls the same, as block-write and block-read.
             // More data can be added to the commands table to indicate the types in either direction.
-            // Coercion does not work for these commands at the moment. Byte count is ignored, but refers to the size of the data read back.
+            // Coercion does not work for these commands at the moment. The byte count is the size of the data read back,
+            // unless the command is also written, in which case it is the size of the data written.
+            CommandEntry {
+                ident: CommandIdent::Verbatim(command),
+                write_kind: CommandWrite::Undefined(_),
+                read_kind: CommandRead::Call(_, _, ty, None),
+                byte_count: CommandByteCount::Count(_, byte_count),
+                ..
+            } => Ok(Some(Self(gen_proc_call_fn(
+                command,
+                ty,
+                Some(*byte_count as usize),
+            )))),
             CommandEntry {
                 ident: CommandIdent::Verbatim(command),
-                read_kind: CommandRead::Call(_, _, ty),
+                read_kind: CommandRead::Call(_, _, ty, None),
                 byte_count: CommandByteCount::Count(_byte_count_span, _),
                 ..
-            } => Some(Self(gen_proc_call_fn(command, ty))),
+            } => Ok(Some(Self(gen_proc_call_fn(command, ty, None)))),
+            // Process calls with a return type write the bytes of the argument,
+            // and read back exactly as many bytes as the byte count, which are converted to the return type.
+            CommandEntry {
+                ident: CommandIdent::Verbatim(command),
+                read_kind: CommandRead::Call(_, _, ty, Some((_, output))),
+                byte_count: CommandByteCount::Count(_, byte_count),
+                ..
+            } => Ok(Some(Self(gen_typed_proc_call_fn(
+                command,
+                ty,
+                output,
+                *byte_count as usize,
+            )))),
             // TODO: This is no different from the above, for now.
             // I expect this to be removed later as very few commands are actually variable sized.
             CommandEntry {
                 ident: CommandIdent::Verbatim(command),
-                read_kind: CommandRead::Call(_, _, ty),
+                read_kind: CommandRead::Call(_, _, ty, None),
                 byte_count: CommandByteCount::Undefined(_),
                 ..
-            } => Some(Self(gen_proc_call_fn(command, ty))),
+            } => Ok(Some(Self(gen_proc_call_fn(command, ty, None)))),
             // TODO: See comment TEST VALIDATION PATTERN (in `ReadCommandFn`).
-            _ => {
-                println!("{}", entry.to_token_stream());
-                unimplemented!("edge cases and invalid entries not yet handled")
-            }
+            _ => Err(syn::Error::new(
+                entry.span(),
+                format!(
+                    "unsupported read definition in command table entry `{}`",
+                    entry.to_token_stream()
+                ),
+            )),
         }
     }
 }
@@ -0,0 +1,97 @@
+use embedded_hal::i2c::{ErrorType, SevenBitAddress};
+use embedded_hal_async::digital::Wait;
+
+use crate::error::{AlertError, SmBusError};
+use crate::smbus::{SmBus, SmBusAddress};
+
+/// The SMBus Host Address, to which devices send Host Notify messages.
+pub const SMBUS_HOST_ADDRESS: SevenBitAddress = 0x08;
+
+/// A device which has requested the attention of the host.
+///
+/// Alerts from both `SMBALERT#` and Host Notify are reported through this type,
+/// so that they can be handled in the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alert<A = SevenBitAddress> {
+    /// The device asserted `SMBALERT#`, and was served by reading the Alert Response Address.
+    Smbalert { address: A },
+    /// The device sent a Host Notify message (6.5.9, Pg. 44), with a device-specific data word.
+    HostNotify { address: A, data: u16 },
+}
+
+impl<A: Copy> Alert<A> {
+    /// The address of the device which raised the alert.
+    pub fn address(&self) -> A {
+        match *self {
+            Self::Smbalert { address } | Self::HostNotify { address, .. } => address,
+        }
+    }
+}
+
+impl Alert<SevenBitAddress> {
+    /// Parse a Host Notify message from the bytes received by the host in target mode,
+    /// following its own address: the address of the notifying device, then the data word (low byte first).
+    ///
+    /// 6.5.9, Pg. 44
+    pub fn from_host_notify<E>(bytes: &[u8]) -> Result<Self, SmBusError<E>> {
+        match *bytes {
+            [address, low, high] => Ok(Self::HostNotify {
+                // The device address is sent in the upper seven bits, the lowest bit is always zero.
+                address: address >> 1,
+                data: u16::from_le_bytes([low, high]),
+            }),
+            _ => Err(SmBusError::ByteCountMismatch {
+                expected: 3,
+                received: bytes.len(),
+            }),
+        }
+    }
+}
+
+/// A bus controller which can act as a target, receiving writes addressed to [`SMBUS_HOST_ADDRESS`].
+///
+/// `embedded-hal` does not define I2C target mode, so this must be implemented for the controller in use.
+#[async_trait::async_trait(?Send)]
+pub trait HostNotifyTarget: ErrorType {
+    /// Wait for a write to the SMBus Host Address, and read the bytes following the address byte into `buf`.
+    ///
+    /// Returns the number of bytes received, which may be larger than `buf` if the message was truncated.
+    async fn receive(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error>;
+}
+
+/// Wait for `SMBALERT#` to be asserted (driven low), then read the Alert Response Address
+/// until every alerting device has been served.
+///
+/// Returns an alert for each device, in the order they responded.
+/// If the line is already asserted, this does not wait.
+pub async fn wait_for_alerts<B, P, A>(
+    bus: &mut B,
+    smbalert: &mut P,
+) -> Result<Vec<Alert<A>>, AlertError<B::Error, P::Error>>
+where
+    B: SmBus<A>,
+    P: Wait,
+    A: SmBusAddress,
+{
+    smbalert.wait_for_low().await.map_err(AlertError::Pin)?;
+    let addresses = bus.alert_responses().await.map_err(AlertError::Bus)?;
+    Ok(addresses
+        .into_iter()
+        .map(|address| Alert::Smbalert { address })
+        .collect())
+}
+
+/// Wait for the next Host Notify message received by `target`.
+pub async fn wait_for_host_notify<T: HostNotifyTarget>(
+    target: &mut T,
+) -> Result<Alert, SmBusError<T::Error>> {
+    let mut buf = [0x00; 3];
+    let len = target.receive(&mut buf).await?;
+    if len != buf.len() {
+        return Err(SmBusError::ByteCountMismatch {
+            expected: buf.len(),
+            received: len,
+        });
+    }
+    Alert::from_host_notify(&buf)
+}
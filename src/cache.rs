@@ -0,0 +1,128 @@
+//! State kept by the host between commands, to avoid reading it back from devices.
+
+use std::collections::BTreeMap;
+
+use embedded_hal::i2c::SevenBitAddress;
+
+use crate::commands::*;
+use crate::format::{Coefficients, VoutMode};
+use crate::smbus::SmBusAddress;
+
+/// State of every device on a bus, as observed through [`PmBus`].
+///
+/// Provide one from [`PmBus::cache`] to enable caching.
+/// It is kept up to date by the writes made through [`PmBus`], so that, for example,
+/// writing VOUT_MODE replaces the cached mode of the selected page.
+/// Writes made by other means must be followed by [`Cache::invalidate`].
+#[derive(Debug, Clone)]
+pub struct Cache<A = SevenBitAddress> {
+    devices: BTreeMap<A, DeviceCache>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct DeviceCache {
+    page: Option<u8>,
+    vout_modes: BTreeMap<u8, VoutMode>,
+    /// Keyed by page, command code, and whether the coefficients are for reading.
+    coefficients: BTreeMap<(u8, u8, bool), Coefficients>,
+}
+
+impl<A: SmBusAddress> Cache<A> {
+    pub fn new() -> Self {
+        Self {
+            devices: BTreeMap::new(),
+        }
+    }
+
+    /// The last page selected on the device at `address`, if known.
+    pub fn page(&self, address: A) -> Option<u8> {
+        self.devices.get(&address)?.page
+    }
+
+    pub fn set_page(&mut self, address: A, page: u8) {
+        self.device(address).page = Some(page);
+    }
+
+    /// The VOUT_MODE of `page` on the device at `address`, if known.
+    pub fn vout_mode(&self, address: A, page: u8) -> Option<VoutMode> {
+        self.devices.get(&address)?.vout_modes.get(&page).copied()
+    }
+
+    pub fn set_vout_mode(&mut self, address: A, page: u8, mode: VoutMode) {
+        self.device(address).vout_modes.insert(page, mode);
+    }
+
+    /// The DIRECT format coefficients of `command` on `page`, for reading (`read == true`) or writing, if known.
+    pub fn coefficients(
+        &self,
+        address: A,
+        page: u8,
+        command: u8,
+        read: bool,
+    ) -> Option<Coefficients> {
+        self.devices
+            .get(&address)?
+            .coefficients
+            .get(&(page, command, read))
+            .copied()
+    }
+
+    pub fn set_coefficients(
+        &mut self,
+        address: A,
+        page: u8,
+        command: u8,
+        read: bool,
+        coefficients: Coefficients,
+    ) {
+        self.device(address)
+            .coefficients
+            .insert((page, command, read), coefficients);
+    }
+
+    /// Forget everything about the device at `address`.
+    pub fn invalidate(&mut self, address: A) {
+        self.devices.remove(&address);
+    }
+
+    /// Forget everything about every device.
+    pub fn clear(&mut self) {
+        self.devices.clear();
+    }
+
+    /// Update the cache after `data` was successfully written to `command` of the device at `address`.
+    ///
+    /// This is called by every write and send method of [`PmBus`].
+    pub fn written(&mut self, address: A, command: u8, data: &[u8]) {
+        let device = self.device(address);
+        match (command, data) {
+            (PAGE, &[page]) => device.page = Some(page),
+            (VOUT_MODE, &[mode]) => match device.page {
+                Some(page) if page != 0xFF => {
+                    device.vout_modes.insert(page, VoutMode::from(mode));
+                }
+                // Every page, or an unknown page, was written.
+                _ => device.vout_modes.clear(),
+            },
+            (
+                RESTORE_DEFAULT_ALL | RESTORE_DEFAULT_CODE | RESTORE_USER_ALL | RESTORE_USER_CODE
+                | PAGE_PLUS_WRITE,
+                _,
+            ) => {
+                device.vout_modes.clear();
+                device.coefficients.clear();
+            }
+            _ => {}
+        }
+    }
+
+    fn device(&mut self, address: A) -> &mut DeviceCache {
+        self.devices.entry(address).or_default()
+    }
+}
+
+impl<A: SmBusAddress> Default for Cache<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
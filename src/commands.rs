@@ -1,16 +1,92 @@
 // TODO: Improve macro hygiene. It doesn't like using `::pmbus::smbus::SmBus`,
 // which would be preferred over generating `crate` type-paths from the macro.
-use crate::smbus::SmBus;
+use crate::cache::Cache;
+use crate::efficiency::EfficiencyData;
+use crate::energy::{EnergyCount, KwhConfig, KwhCounter};
+use crate::error::PmBusError;
+use crate::format::{Coefficients, NumericFormat, VidCode, VoutMode};
+use crate::on_off_config::OnOffConfig;
+use crate::operation::Operation;
+use crate::revision::PmBusRevision;
+use crate::smbus::{SmBus, SmBusAddress};
+use crate::status::{
+    StatusByte, StatusCml, StatusFans12, StatusFans34, StatusInput, StatusIout, StatusOther,
+    StatusTemperature, StatusVout, StatusWord,
+};
+use crate::units::{Amps, Celsius, Hertz, Milliseconds, Percent, Rpm, Volts, Watts};
+
+mod ext;
+
+pub use self::ext::PmBusExt;
+
+/// How a command is written, see [`CommandInfo::write`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteKind {
+    /// Send Byte, the command code alone.
+    Send,
+    /// The command code followed by data.
+    Write,
+}
+
+/// How a command is read, see [`CommandInfo::read`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadKind {
+    /// The command code is written, and data is read back.
+    Read,
+    /// Block Write-Block Read Process Call.
+    Call,
+}
+
+/// A row of the command table, available at runtime through [`COMMANDS`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandInfo {
+    pub code: u8,
+    pub name: &'static str,
+    pub write: Option<WriteKind>,
+    pub read: Option<ReadKind>,
+    /// Number of data bytes, or `None` for variable-sized blocks and manufacturer specific commands.
+    pub size: Option<usize>,
+}
+
+impl CommandInfo {
+    /// Look up a command by its code, returning `None` for reserved codes.
+    pub fn get(code: u8) -> Option<&'static Self> {
+        COMMANDS.iter().find(|info| info.code == code)
+    }
+
+    /// Whether the data is transferred as a block, with a leading byte count.
+    ///
+    /// This matches the transactions used by [`PmBus`].
+    pub fn is_block(&self) -> bool {
+        !matches!(self.size, Some(0 | 1 | 2 | 4 | 8))
+    }
+}
+
+/// Output voltage commands which hold data relative to VOUT_COMMAND when the [`VoutMode`] is relative.
+///
+/// Part II, 8.3
+pub const RELATIVE_VOUT_COMMANDS: &[u8] = &[
+    VOUT_MARGIN_HIGH,
+    VOUT_MARGIN_LOW,
+    VOUT_OV_FAULT_LIMIT,
+    VOUT_OV_WARN_LIMIT,
+    VOUT_UV_WARN_LIMIT,
+    VOUT_UV_FAULT_LIMIT,
+    POWER_GOOD_ON,
+    POWER_GOOD_OFF,
+];
 
 // This table follows the same order as Appendix I. Command Summary, Table 31, sequential byte order.
 //
 // <https://pmbusprod.wpenginepowered.com/wp-content/uploads/2022/01/PMBus-Specification-Rev-1-3-1-Part-II-20150313.pdf>
 //
 // TODO: Process call definitions are probably incorrect. The table does not correspond exactly with the sizes
-// defined in the sections specific to each command code. For example, `COEFFICIENTS` is known to be wrong,
-// the write data is supposed to be two bytes long, consisting of a command code from this table, and another byte for indicating encoding.
-// The response is five bytes, which are defined in another section of the document.
-// This is not an immediate concern, the next step for this library is to define types for all
+// defined in the sections specific to each command code.
+// Calls written as `call: In -> Out` write the bytes of `In` and convert exactly `N_BYTES` bytes into `Out`.
+// Other calls read back exactly `N_BYTES` bytes, unless the command is also written (as SMBALERT_MASK is),
+// in which case `N_BYTES` is the size of the write and the block read back may be of any size.
+// `COEFFICIENTS` is defined this way, writing a command code and a byte selecting read (`1`) or write (`0`) coefficients.
+// The remaining calls are not an immediate concern, the next step for this library is to define types for all
 // commands used; Fourier can define types to encode to/from the bytes in either direction.
 //
 // TODO: Reading into fixed-size byte arrays is up next. However, consider implementing types for conversion beforehand to see how
@@ -18,11 +94,18 @@ use crate::smbus::SmBus;
 // defined here, inline in the table. Allowing type-associated constructor functions is also planned.
 // For now, all read commands which return slices or arrays are treated as byte vectors.
 //
+// Output voltages are marked `vout` in place of a type. They are read and written in `Volts`,
+// converted using the VOUT_MODE of the selected page (see `PmBusExt::decode_vout` and `PmBusExt::encode_vout`).
+//
+// Other physical quantities are marked `numeric` followed by their unit (see `crate::units`),
+// and converted using the numeric format of the device (see `PmBus::numeric_format`).
+// A scale may follow, which the value on the bus is multiplied by to give the quantity.
+//
 //  | BYTE | COMMAND                   | WRITE_TYPE    | READ_TYPE       | N_BYTES |
 pmbus_macros::impl_commands! {
     | 0x00 | PAGE                      | write: u8     | read: u8        | 1  |,
-    | 0x01 | OPERATION                 | write: u8     | read: u8        | 1  |,
-    | 0x02 | ON_OFF_CONFIG             | write: u8     | read: u8        | 1  |,
+    | 0x01 | OPERATION                 | write: Operation | read: Operation | 1  |,
+    | 0x02 | ON_OFF_CONFIG             | write: OnOffConfig | read: OnOffConfig | 1  |,
     | 0x03 | CLEAR_FAULTS              | send          | _               | 0  |,
     | 0x04 | PHASE                     | write: u8     | read: u8        | 1  |,
     | 0x05 | PAGE_PLUS_WRITE           | write: &[u8]  | _               | _  |,
@@ -52,82 +135,82 @@ pmbus_macros::impl_commands! {
     | 0x1D | _                         | _             | _               | _  |,
     | 0x1E | _                         | _             | _               | _  |,
     | 0x1F | _                         | _             | _               | _  |,
-    | 0x20 | VOUT_MODE                 | write: u8     | read: u8        | 1  |,
-    | 0x21 | VOUT_COMMAND              | write: u16    | read: u16       | 2  |,
+    | 0x20 | VOUT_MODE                 | write: VoutMode | read: VoutMode  | 1  |,
+    | 0x21 | VOUT_COMMAND              | write: vout   | read: vout      | 2  |,
     | 0x22 | VOUT_TRIM                 | write: u16    | read: u16       | 2  |,
     | 0x23 | VOUT_CAL_OFFSET           | write: u16    | read: u16       | 2  |,
-    | 0x24 | VOUT_MAX                  | write: u16    | read: u16       | 2  |,
-    | 0x25 | VOUT_MARGIN_HIGH          | write: u16    | read: u16       | 2  |,
-    | 0x26 | VOUT_MARGIN_LOW           | write: u16    | read: u16       | 2  |,
+    | 0x24 | VOUT_MAX                  | write: vout   | read: vout      | 2  |,
+    | 0x25 | VOUT_MARGIN_HIGH          | write: vout   | read: vout      | 2  |,
+    | 0x26 | VOUT_MARGIN_LOW           | write: vout   | read: vout      | 2  |,
     | 0x27 | VOUT_TRANSITION_RATE      | write: u16    | read: u16       | 2  |,
     | 0x28 | VOUT_DROOP                | write: u16    | read: u16       | 2  |,
     | 0x29 | VOUT_SCALE_LOOP           | write: u16    | read: u16       | 2  |,
     | 0x2A | VOUT_SCALE_MONITOR        | write: u16    | read: u16       | 2  |,
-    | 0x2B | VOUT_MIN                  | write: u16    | read: u16       | 2  |,
+    | 0x2B | VOUT_MIN                  | write: vout   | read: vout      | 2  |,
     | 0x2C | _                         | _             | _               | _  |,
     | 0x2D | _                         | _             | _               | _  |,
     | 0x2E | _                         | _             | _               | _  |,
     | 0x2F | _                         | _             | _               | _  |,
-    | 0x30 | COEFFICIENTS              | _             | call: &[u8; 5]  | 5  |,
-    | 0x31 | POUT_MAX                  | write: u16    | read: u16       | 2  |,
-    | 0x32 | MAX_DUTY                  | write: u16    | read: u16       | 2  |,
-    | 0x33 | FREQUENCY_SWITCH          | write: u16    | read: u16       | 2  |,
+    | 0x30 | COEFFICIENTS              | _             | call: [u8; 2] -> Coefficients | 5  |,
+    | 0x31 | POUT_MAX                  | write: numeric Watts | read: numeric Watts | 2  |,
+    | 0x32 | MAX_DUTY                  | write: numeric Percent | read: numeric Percent | 2  |,
+    | 0x33 | FREQUENCY_SWITCH          | write: numeric Hertz * 1000 | read: numeric Hertz * 1000 | 2  |,
     | 0x34 | POWER_MODE                | write: u8     | read: u8        | 1  |,
-    | 0x35 | VIN_ON                    | write: u16    | read: u16       | 2  |,
-    | 0x36 | VIN_OFF                   | write: u16    | read: u16       | 2  |,
+    | 0x35 | VIN_ON                    | write: numeric Volts | read: numeric Volts | 2  |,
+    | 0x36 | VIN_OFF                   | write: numeric Volts | read: numeric Volts | 2  |,
     | 0x37 | INTERLEAVE                | write: u16    | read: u16       | 2  |,
     | 0x38 | IOUT_CAL_GAIN             | write: u16    | read: u16       | 2  |,
-    | 0x39 | IOUT_CAL_OFFSET           | write: u16    | read: u16       | 2  |,
+    | 0x39 | IOUT_CAL_OFFSET           | write: numeric Amps | read: numeric Amps | 2  |,
     | 0x3A | FAN_CONFIG_1_2            | write: u8     | read: u8        | 1  |,
     | 0x3B | FAN_COMMAND_1             | write: u16    | read: u16       | 2  |,
     | 0x3C | FAN_COMMAND_2             | write: u16    | read: u16       | 2  |,
     | 0x3D | FAN_CONFIG_3_4            | write: u8     | read: u8        | 1  |,
     | 0x3E | FAN_COMMAND_3             | write: u16    | read: u16       | 2  |,
     | 0x3F | FAN_COMMAND_4             | write: u16    | read: u16       | 2  |,
-    | 0x40 | VOUT_OV_FAULT_LIMIT       | write: u16    | read: u16       | 2  |,
+    | 0x40 | VOUT_OV_FAULT_LIMIT       | write: vout   | read: vout      | 2  |,
     | 0x41 | VOUT_OV_FAULT_RESPONSE    | write: u8     | read: u8        | 1  |,
-    | 0x42 | VOUT_OV_WARN_LIMIT        | write: u16    | read: u16       | 2  |,
-    | 0x43 | VOUT_UV_WARN_LIMIT        | write: u16    | read: u16       | 2  |,
-    | 0x44 | VOUT_UV_FAULT_LIMIT       | write: u16    | read: u16       | 2  |,
+    | 0x42 | VOUT_OV_WARN_LIMIT        | write: vout   | read: vout      | 2  |,
+    | 0x43 | VOUT_UV_WARN_LIMIT        | write: vout   | read: vout      | 2  |,
+    | 0x44 | VOUT_UV_FAULT_LIMIT       | write: vout   | read: vout      | 2  |,
     | 0x45 | VOUT_UV_FAULT_RESPONSE    | write: u8     | read: u8        | 1  |,
-    | 0x46 | IOUT_OC_FAULT_LIMIT       | write: u16    | read: u16       | 2  |,
+    | 0x46 | IOUT_OC_FAULT_LIMIT       | write: numeric Amps | read: numeric Amps | 2  |,
     | 0x47 | IOUT_OC_FAULT_RESPONSE    | write: u8     | read: u8        | 1  |,
-    | 0x48 | IOUT_OC_LV_FAULT_LIMIT    | write: u16    | read: u16       | 2  |,
+    | 0x48 | IOUT_OC_LV_FAULT_LIMIT    | write: numeric Amps | read: numeric Amps | 2  |,
     | 0x49 | IOUT_OC_LV_FAULT_RESPONSE | write: u8     | read: u8        | 1  |,
-    | 0x4A | IOUT_OC_WARN_LIMIT        | write: u16    | read: u16       | 2  |,
-    | 0x4B | IOUT_UC_FAULT_LIMIT       | write: u16    | read: u16       | 2  |,
+    | 0x4A | IOUT_OC_WARN_LIMIT        | write: numeric Amps | read: numeric Amps | 2  |,
+    | 0x4B | IOUT_UC_FAULT_LIMIT       | write: numeric Amps | read: numeric Amps | 2  |,
     | 0x4C | IOUT_UC_FAULT_RESPONSE    | write: u8     | read: u8        | 1  |,
     | 0x4D | _                         | _             | _               | _  |,
     | 0x4E | _                         | _             | _               | _  |,
-    | 0x4F | OT_FAULT_LIMIT            | write: u16    | read: u16       | 2  |,
+    | 0x4F | OT_FAULT_LIMIT            | write: numeric Celsius | read: numeric Celsius | 2  |,
     | 0x50 | OT_FAULT_RESPONSE         | write: u8     | read: u8        | 1  |,
-    | 0x51 | OT_WARN_LIMIT             | write: u16    | read: u16       | 2  |,
-    | 0x52 | UT_WARN_LIMIT             | write: u16    | read: u16       | 2  |,
-    | 0x53 | UT_FAULT_LIMIT            | write: u16    | read: u16       | 2  |,
+    | 0x51 | OT_WARN_LIMIT             | write: numeric Celsius | read: numeric Celsius | 2  |,
+    | 0x52 | UT_WARN_LIMIT             | write: numeric Celsius | read: numeric Celsius | 2  |,
+    | 0x53 | UT_FAULT_LIMIT            | write: numeric Celsius | read: numeric Celsius | 2  |,
     | 0x54 | UT_FAULT_RESPONSE         | write: u8     | read: u8        | 1  |,
-    | 0x55 | VIN_OV_FAULT_LIMIT        | write: u16    | read: u16       | 2  |,
+    | 0x55 | VIN_OV_FAULT_LIMIT        | write: numeric Volts | read: numeric Volts | 2  |,
     | 0x56 | VIN_OV_FAULT_RESPONSE     | write: u8     | read: u8        | 1  |,
-    | 0x57 | VIN_OV_WARN_LIMIT         | write: u16    | read: u16       | 2  |,
-    | 0x58 | VIN_UV_WARN_LIMIT         | write: u16    | read: u16       | 2  |,
-    | 0x59 | VIN_UV_FAULT_LIMIT        | write: u16    | read: u16       | 2  |,
+    | 0x57 | VIN_OV_WARN_LIMIT         | write: numeric Volts | read: numeric Volts | 2  |,
+    | 0x58 | VIN_UV_WARN_LIMIT         | write: numeric Volts | read: numeric Volts | 2  |,
+    | 0x59 | VIN_UV_FAULT_LIMIT        | write: numeric Volts | read: numeric Volts | 2  |,
     | 0x5A | VIN_UV_FAULT_RESPONSE     | write: u8     | read: u8        | 1  |,
-    | 0x5B | IIN_OC_FAULT_LIMIT        | write: u16    | read: u16       | 2  |,
+    | 0x5B | IIN_OC_FAULT_LIMIT        | write: numeric Amps | read: numeric Amps | 2  |,
     | 0x5C | IIN_OC_FAULT_RESPONSE     | write: u8     | read: u8        | 1  |,
-    | 0x5D | IIN_OC_WARN_LIMIT         | write: u16    | read: u16       | 2  |,
-    | 0x5E | POWER_GOOD_ON             | write: u16    | read: u16       | 2  |,
-    | 0x5F | POWER_GOOD_OFF            | write: u16    | read: u16       | 2  |,
-    | 0x60 | TON_DELAY                 | write: u16    | read: u16       | 2  |,
-    | 0x61 | TON_RISE                  | write: u16    | read: u16       | 2  |,
-    | 0x62 | TON_MAX_FAULT_LIMIT       | write: u16    | read: u16       | 2  |,
+    | 0x5D | IIN_OC_WARN_LIMIT         | write: numeric Amps | read: numeric Amps | 2  |,
+    | 0x5E | POWER_GOOD_ON             | write: vout   | read: vout      | 2  |,
+    | 0x5F | POWER_GOOD_OFF            | write: vout   | read: vout      | 2  |,
+    | 0x60 | TON_DELAY                 | write: numeric Milliseconds | read: numeric Milliseconds | 2  |,
+    | 0x61 | TON_RISE                  | write: numeric Milliseconds | read: numeric Milliseconds | 2  |,
+    | 0x62 | TON_MAX_FAULT_LIMIT       | write: numeric Milliseconds | read: numeric Milliseconds | 2  |,
     | 0x63 | TON_MAX_FAULT_RESPONSE    | write: u8     | read: u8        | 1  |,
-    | 0x64 | TOFF_DELAY                | write: u16    | read: u16       | 2  |,
-    | 0x65 | TOFF_FALL                 | write: u16    | read: u16       | 2  |,
-    | 0x66 | TOFF_MAX_WARN_LIMIT       | write: u16    | read: u16       | 2  |,
+    | 0x64 | TOFF_DELAY                | write: numeric Milliseconds | read: numeric Milliseconds | 2  |,
+    | 0x65 | TOFF_FALL                 | write: numeric Milliseconds | read: numeric Milliseconds | 2  |,
+    | 0x66 | TOFF_MAX_WARN_LIMIT       | write: numeric Milliseconds | read: numeric Milliseconds | 2  |,
     | 0x67 | _                         | _             | _               | _  |, // (Was Used In Revision 1.0),
-    | 0x68 | POUT_OP_FAULT_LIMIT       | write: u16    | read: u16       | 2  |,
+    | 0x68 | POUT_OP_FAULT_LIMIT       | write: numeric Watts | read: numeric Watts | 2  |,
     | 0x69 | POUT_OP_FAULT_RESPONSE    | write: u8     | read: u8        | 1  |,
-    | 0x6A | POUT_OP_WARN_LIMIT        | write: u16    | read: u16       | 2  |,
-    | 0x6B | PIN_OP_WARN_LIMIT         | write: u16    | read: u16       | 2  |,
+    | 0x6A | POUT_OP_WARN_LIMIT        | write: numeric Watts | read: numeric Watts | 2  |,
+    | 0x6B | PIN_OP_WARN_LIMIT         | write: numeric Watts | read: numeric Watts | 2  |,
     | 0x6C | _                         | _             | _               | _  |,
     | 0x6D | _                         | _             | _               | _  |,
     | 0x6E | _                         | _             | _               | _  |,
@@ -140,39 +223,39 @@ pmbus_macros::impl_commands! {
     | 0x75 | _                         | _             | _               | _  |,
     | 0x76 | _                         | _             | _               | _  |,
     | 0x77 | _                         | _             | _               | _  |,
-    | 0x78 | STATUS_BYTE               | write: u8     | read: u8        | 1  |,
-    | 0x79 | STATUS_WORD               | write: u16    | read: u16       | 2  |,
-    | 0x7A | STATUS_VOUT               | write: u8     | read: u8        | 1  |,
-    | 0x7B | STATUS_IOUT               | write: u8     | read: u8        | 1  |,
-    | 0x7C | STATUS_INPUT              | write: u8     | read: u8        | 1  |,
-    | 0x7D | STATUS_TEMPERATURE        | write: u8     | read: u8        | 1  |,
-    | 0x7E | STATUS_CML                | write: u8     | read: u8        | 1  |,
-    | 0x7F | STATUS_OTHER              | write: u8     | read: u8        | 1  |,
+    | 0x78 | STATUS_BYTE               | write: StatusByte | read: StatusByte | 1  |,
+    | 0x79 | STATUS_WORD               | write: StatusWord | read: StatusWord | 2  |,
+    | 0x7A | STATUS_VOUT               | write: StatusVout | read: StatusVout | 1  |,
+    | 0x7B | STATUS_IOUT               | write: StatusIout | read: StatusIout | 1  |,
+    | 0x7C | STATUS_INPUT              | write: StatusInput | read: StatusInput | 1  |,
+    | 0x7D | STATUS_TEMPERATURE        | write: StatusTemperature | read: StatusTemperature | 1  |,
+    | 0x7E | STATUS_CML                | write: StatusCml | read: StatusCml | 1  |,
+    | 0x7F | STATUS_OTHER              | write: StatusOther | read: StatusOther | 1  |,
     | 0x80 | STATUS_MFR_SPECIFIC       | write: u8     | read: u8        | 1  |,
-    | 0x81 | STATUS_FANS_1_2           | write: u8     | read: u8        | 1  |,
-    | 0x82 | STATUS_FANS_3_4           | write: u8     | read: u8        | 1  |,
-    | 0x83 | READ_KWH_IN               | _             | read: Vec<u8>   | 4  |,
-    | 0x84 | READ_KWH_OUT              | _             | read: Vec<u8>   | 4  |,
-    | 0x85 | READ_KWH_CONFIG           | write: u16    | read: u16       | 2  |,
-    | 0x86 | READ_EIN                  | _             | read: Vec<u8>   | 5  |,
-    | 0x87 | READ_EOUT                 | _             | read: Vec<u8>   | 5  |,
-    | 0x88 | READ_VIN                  | _             | read: u16       | 2  |,
-    | 0x89 | READ_IIN                  | _             | read: u16       | 2  |,
-    | 0x8A | READ_VCAP                 | _             | read: u16       | 2  |,
-    | 0x8B | READ_VOUT                 | _             | read: u16       | 2  |,
-    | 0x8C | READ_IOUT                 | _             | read: u16       | 2  |,
-    | 0x8D | READ_TEMPERATURE_1        | _             | read: u16       | 2  |,
-    | 0x8E | READ_TEMPERATURE_2        | _             | read: u16       | 2  |,
-    | 0x8F | READ_TEMPERATURE_3        | _             | read: u16       | 2  |,
-    | 0x90 | READ_FAN_SPEED_1          | _             | read: u16       | 2  |,
-    | 0x91 | READ_FAN_SPEED_2          | _             | read: u16       | 2  |,
-    | 0x92 | READ_FAN_SPEED_3          | _             | read: u16       | 2  |,
-    | 0x93 | READ_FAN_SPEED_4          | _             | read: u16       | 2  |,
-    | 0x94 | READ_DUTY_CYCLE           | _             | read: u16       | 2  |,
-    | 0x95 | READ_FREQUENCY            | _             | read: u16       | 2  |,
-    | 0x96 | READ_POUT                 | _             | read: u16       | 2  |,
-    | 0x97 | READ_PIN                  | _             | read: u16       | 2  |,
-    | 0x98 | PMBUS_REVISION            | _             | read: u8        | 1  |,
+    | 0x81 | STATUS_FANS_1_2           | write: StatusFans12 | read: StatusFans12 | 1  |,
+    | 0x82 | STATUS_FANS_3_4           | write: StatusFans34 | read: StatusFans34 | 1  |,
+    | 0x83 | READ_KWH_IN               | _             | read: KwhCounter | 4  |,
+    | 0x84 | READ_KWH_OUT              | _             | read: KwhCounter | 4  |,
+    | 0x85 | READ_KWH_CONFIG           | write: KwhConfig | read: KwhConfig | 2  |,
+    | 0x86 | READ_EIN                  | _             | read: EnergyCount | 6  |,
+    | 0x87 | READ_EOUT                 | _             | read: EnergyCount | 6  |,
+    | 0x88 | READ_VIN                  | _             | read: numeric Volts | 2  |,
+    | 0x89 | READ_IIN                  | _             | read: numeric Amps | 2  |,
+    | 0x8A | READ_VCAP                 | _             | read: numeric Volts | 2  |,
+    | 0x8B | READ_VOUT                 | _             | read: vout      | 2  |,
+    | 0x8C | READ_IOUT                 | _             | read: numeric Amps | 2  |,
+    | 0x8D | READ_TEMPERATURE_1        | _             | read: numeric Celsius | 2  |,
+    | 0x8E | READ_TEMPERATURE_2        | _             | read: numeric Celsius | 2  |,
+    | 0x8F | READ_TEMPERATURE_3        | _             | read: numeric Celsius | 2  |,
+    | 0x90 | READ_FAN_SPEED_1          | _             | read: numeric Rpm | 2  |,
+    | 0x91 | READ_FAN_SPEED_2          | _             | read: numeric Rpm | 2  |,
+    | 0x92 | READ_FAN_SPEED_3          | _             | read: numeric Rpm | 2  |,
+    | 0x93 | READ_FAN_SPEED_4          | _             | read: numeric Rpm | 2  |,
+    | 0x94 | READ_DUTY_CYCLE           | _             | read: numeric Percent | 2  |,
+    | 0x95 | READ_FREQUENCY            | _             | read: numeric Hertz * 1000 | 2  |,
+    | 0x96 | READ_POUT                 | _             | read: numeric Watts | 2  |,
+    | 0x97 | READ_PIN                  | _             | read: numeric Watts | 2  |,
+    | 0x98 | PMBUS_REVISION            | _             | read: PmBusRevision | 1  |,
     | 0x99 | MFR_ID                    | write: &[u8]  | read: Vec<u8>   | _  |,
     | 0x9A | MFR_MODEL                 | write: &[u8]  | read: Vec<u8>   | _  |,
     | 0x9B | MFR_REVISION              | write: &[u8]  | read: Vec<u8>   | _  |,
@@ -180,18 +263,18 @@ pmbus_macros::impl_commands! {
     | 0x9D | MFR_DATE                  | write: &[u8]  | read: Vec<u8>   | _  |,
     | 0x9E | MFR_SERIAL                | write: &[u8]  | read: Vec<u8>   | _  |,
     | 0x9F | APP_PROFILE_SUPPORT       | _             | read: Vec<u8>   | _  |,
-    | 0xA0 | MFR_VIN_MIN               | _             | read: u16       | 2  |,
-    | 0xA1 | MFR_VIN_MAX               | _             | read: u16       | 2  |,
-    | 0xA2 | MFR_IIN_MAX               | _             | read: u16       | 2  |,
-    | 0xA3 | MFR_PIN_MAX               | _             | read: u16       | 2  |,
-    | 0xA4 | MFR_VOUT_MIN              | _             | read: u16       | 2  |,
-    | 0xA5 | MFR_VOUT_MAX              | _             | read: u16       | 2  |,
-    | 0xA6 | MFR_IOUT_MAX              | _             | read: u16       | 2  |,
-    | 0xA7 | MFR_POUT_MAX              | _             | read: u16       | 2  |,
-    | 0xA8 | MFR_TAMBIENT_MAX          | _             | read: u16       | 2  |,
-    | 0xA9 | MFR_TAMBIENT_MIN          | _             | read: u16       | 2  |,
-    | 0xAA | MFR_EFFICIENCY_LL         | _             | read: Vec<u8>   | 14 |,
-    | 0xAB | MFR_EFFICIENCY_HL         | _             | read: Vec<u8>   | 14 |,
+    | 0xA0 | MFR_VIN_MIN               | _             | read: numeric Volts | 2  |,
+    | 0xA1 | MFR_VIN_MAX               | _             | read: numeric Volts | 2  |,
+    | 0xA2 | MFR_IIN_MAX               | _             | read: numeric Amps | 2  |,
+    | 0xA3 | MFR_PIN_MAX               | _             | read: numeric Watts | 2  |,
+    | 0xA4 | MFR_VOUT_MIN              | _             | read: vout      | 2  |,
+    | 0xA5 | MFR_VOUT_MAX              | _             | read: vout      | 2  |,
+    | 0xA6 | MFR_IOUT_MAX              | _             | read: numeric Amps | 2  |,
+    | 0xA7 | MFR_POUT_MAX              | _             | read: numeric Watts | 2  |,
+    | 0xA8 | MFR_TAMBIENT_MAX          | _             | read: numeric Celsius | 2  |,
+    | 0xA9 | MFR_TAMBIENT_MIN          | _             | read: numeric Celsius | 2  |,
+    | 0xAA | MFR_EFFICIENCY_LL         | _             | read: EfficiencyData | 14 |,
+    | 0xAB | MFR_EFFICIENCY_HL         | _             | read: EfficiencyData | 14 |,
     | 0xAC | MFR_PIN_ACCURACY          | _             | read: u8        | 1  |,
     | 0xAD | IC_DEVICE_ID              | _             | read: Vec<u8>   | _  |,
     | 0xAE | IC_DEVICE_REV             | _             | read: Vec<u8>   | _  |,
@@ -212,9 +295,9 @@ pmbus_macros::impl_commands! {
     | 0xBD | USER_DATA_13              | write: &[u8]  | read: Vec<u8>   | _  |,
     | 0xBE | USER_DATA_14              | write: &[u8]  | read: Vec<u8>   | _  |,
     | 0xBF | USER_DATA_15              | write: &[u8]  | read: Vec<u8>   | _  |,
-    | 0xC0 | MFR_MAX_TEMP_1            | write: u16    | read: u16       | 2  |,
-    | 0xC1 | MFR_MAX_TEMP_2            | write: u16    | read: u16       | 2  |,
-    | 0xC2 | MFR_MAX_TEMP_3            | write: u16    | read: u16       | 2  |,
+    | 0xC0 | MFR_MAX_TEMP_1            | write: numeric Celsius | read: numeric Celsius | 2  |,
+    | 0xC1 | MFR_MAX_TEMP_2            | write: numeric Celsius | read: numeric Celsius | 2  |,
+    | 0xC2 | MFR_MAX_TEMP_3            | write: numeric Celsius | read: numeric Celsius | 2  |,
     | 0xC3 | _                         | _             | _               | _  |,
     | 0xC4 | MFR_SPECIFIC_C4           | !             | !               | !  |,
     | 0xC5 | MFR_SPECIFIC_C5           | !             | !               | !  |,
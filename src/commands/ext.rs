@@ -0,0 +1,416 @@
+use crate::error::{optional, FormatError, PmBusError};
+use crate::format::{Coefficients, Direct, Linear11, NumericFormat, Vid, VoutMode};
+use crate::identity::{decode_text, DeviceIdentity, MfrDate};
+use crate::operation::{Margin, Operation};
+use crate::smbus::SmBusAddress;
+use crate::status::{FaultReport, StatusRegister, StatusWord};
+
+use super::{
+    PmBus, MFR_DATE, MFR_ID, MFR_LOCATION, MFR_MODEL, MFR_REVISION, MFR_SERIAL, PAGE,
+    RELATIVE_VOUT_COMMANDS, VOUT_COMMAND, VOUT_MODE,
+};
+
+/// Define the `PmBusExt` trait over the `PmBus` in scope where it is invoked,
+/// with `async` methods awaiting the bus, or with blocking methods.
+///
+/// Both [`PmBusExt`] and [`blocking::PmBusExt`] are defined by this, so that they cannot drift apart.
+macro_rules! pmbus_ext_trait {
+    ($(#[$attr:meta])* [$($async:ident)?] [$($await:tt)*]) => {
+        $(#[$attr])*
+        pub trait PmBusExt<A: SmBusAddress>: PmBus<A> {
+            /// The selected page, taken from the [`cache`](PmBus::cache) when possible.
+            $($async)? fn selected_page(&mut self, address: A) -> Result<u8, PmBusError<Self::Error>> {
+                if let Some(page) = self.cache().and_then(|cache| cache.page(address)) {
+                    return Ok(page);
+                }
+                let page = self.read_byte(address, PAGE)$($await)* ?;
+                if let Some(cache) = self.cache() {
+                    cache.set_page(address, page);
+                }
+                Ok(page)
+            }
+
+            /// The VOUT_MODE of the selected page, which gives the format of VOUT-family commands.
+            ///
+            /// The mode is taken from the [`cache`](PmBus::cache) when possible.
+            $($async)? fn vout_mode(&mut self, address: A) -> Result<VoutMode, PmBusError<Self::Error>> {
+                if self.cache().is_none() {
+                    let mode = self.read_byte(address, VOUT_MODE)$($await)* ?;
+                    return Ok(VoutMode::from(mode));
+                }
+                let page = self.selected_page(address)$($await)* ?;
+                if let Some(mode) = self.cache().and_then(|cache| cache.vout_mode(address, page)) {
+                    return Ok(mode);
+                }
+                let mode = VoutMode::from(self.read_byte(address, VOUT_MODE)$($await)* ?);
+                if let Some(cache) = self.cache() {
+                    cache.set_vout_mode(address, page, mode);
+                }
+                Ok(mode)
+            }
+
+            /// The DIRECT format coefficients of `command` on the selected page,
+            /// for reading (`read == true`) or writing it.
+            ///
+            /// These are the [`fixed_coefficients`](PmBus::fixed_coefficients) if there are any,
+            /// otherwise they are taken from the [`cache`](PmBus::cache) or read with COEFFICIENTS.
+            $($async)? fn coefficients(
+                &mut self,
+                address: A,
+                command: u8,
+                read: bool,
+            ) -> Result<Coefficients, PmBusError<Self::Error>> {
+                if let Some(coefficients) = self.fixed_coefficients(address, command, read) {
+                    return Ok(coefficients);
+                }
+                if self.cache().is_none() {
+                    return self.call_coefficients(address, [command, read as u8])$($await)*;
+                }
+                let page = self.selected_page(address)$($await)* ?;
+                if let Some(coefficients) = self
+                    .cache()
+                    .and_then(|cache| cache.coefficients(address, page, command, read))
+                {
+                    return Ok(coefficients);
+                }
+                let coefficients = self.call_coefficients(address, [command, read as u8])$($await)* ?;
+                if let Some(cache) = self.cache() {
+                    cache.set_coefficients(address, page, command, read, coefficients);
+                }
+                Ok(coefficients)
+            }
+
+            /// Decode the raw data of the output voltage `command` in volts, using the [`vout_mode`](PmBusExt::vout_mode).
+            ///
+            /// Relative data is resolved against VOUT_COMMAND, which is read for the purpose.
+            $($async)? fn decode_vout(
+                &mut self,
+                address: A,
+                command: u8,
+                raw: u16,
+            ) -> Result<f64, PmBusError<Self::Error>> {
+                let mode = self.vout_mode(address)$($await)* ?;
+                if !(mode.is_relative() && RELATIVE_VOUT_COMMANDS.contains(&command)) {
+                    return self.decode_vout_with(address, mode.absolute(), command, raw)$($await)*;
+                }
+                let per_mille = self.decode_vout_with(address, mode, command, raw)$($await)* ?;
+                let reference = self.read_word(address, VOUT_COMMAND)$($await)* ?;
+                let reference = self
+                    .decode_vout_with(address, mode.absolute(), VOUT_COMMAND, reference)$($await)* ?;
+                Ok(reference * (1.0 + per_mille / 1000.0))
+            }
+
+            /// Encode `volts` as the raw data of the output voltage `command`, using the [`vout_mode`](PmBusExt::vout_mode).
+            ///
+            /// Relative data is computed against VOUT_COMMAND, which is read for the purpose.
+            $($async)? fn encode_vout(
+                &mut self,
+                address: A,
+                command: u8,
+                volts: f64,
+            ) -> Result<u16, PmBusError<Self::Error>> {
+                let mode = self.vout_mode(address)$($await)* ?;
+                if !(mode.is_relative() && RELATIVE_VOUT_COMMANDS.contains(&command)) {
+                    return self.encode_vout_with(address, mode.absolute(), command, volts)$($await)*;
+                }
+                let reference = self.read_word(address, VOUT_COMMAND)$($await)* ?;
+                let reference = self
+                    .decode_vout_with(address, mode.absolute(), VOUT_COMMAND, reference)$($await)* ?;
+                let per_mille = (volts / reference - 1.0) * 1000.0;
+                self.encode_vout_with(address, mode, command, per_mille)$($await)*
+            }
+
+            /// Decode the raw data of `command` in the given `mode`, ignoring which commands are relative.
+            $($async)? fn decode_vout_with(
+                &mut self,
+                address: A,
+                mode: VoutMode,
+                command: u8,
+                raw: u16,
+            ) -> Result<f64, PmBusError<Self::Error>> {
+                match mode {
+                    VoutMode::Direct { .. } => {
+                        let coefficients = self.coefficients(address, command, true)$($await)* ?;
+                        Ok(Direct(raw).to_f64(coefficients)?)
+                    }
+                    VoutMode::Vid { code } => {
+                        let table = self.vid_table(address, code).ok_or(FormatError::Unsupported)?;
+                        Ok(Vid(raw).to_f64(table)?)
+                    }
+                    mode => Ok(mode.decode(raw)?),
+                }
+            }
+
+            /// Encode `value` as the raw data of `command` in the given `mode`, ignoring which commands are relative.
+            $($async)? fn encode_vout_with(
+                &mut self,
+                address: A,
+                mode: VoutMode,
+                command: u8,
+                value: f64,
+            ) -> Result<u16, PmBusError<Self::Error>> {
+                match mode {
+                    VoutMode::Direct { .. } => {
+                        let coefficients = self.coefficients(address, command, false)$($await)* ?;
+                        Ok(Direct::from_f64(value, coefficients)?.into())
+                    }
+                    VoutMode::Vid { code } => {
+                        let table = self.vid_table(address, code).ok_or(FormatError::Unsupported)?;
+                        Ok(Vid::from_f64(value, table)?.into())
+                    }
+                    mode => Ok(mode.encode(value)?),
+                }
+            }
+
+            /// Decode the raw data of `command` in its [`numeric_format`](PmBus::numeric_format).
+            $($async)? fn decode_numeric(
+                &mut self,
+                address: A,
+                command: u8,
+                raw: u16,
+            ) -> Result<f64, PmBusError<Self::Error>> {
+                match self.numeric_format(address, command) {
+                    NumericFormat::Linear11 => Ok(Linear11(raw).to_f64()),
+                    NumericFormat::Direct => {
+                        let coefficients = self.coefficients(address, command, true)$($await)* ?;
+                        Ok(Direct(raw).to_f64(coefficients)?)
+                    }
+                }
+            }
+
+            /// Encode `value` as the raw data of `command` in its [`numeric_format`](PmBus::numeric_format).
+            $($async)? fn encode_numeric(
+                &mut self,
+                address: A,
+                command: u8,
+                value: f64,
+            ) -> Result<u16, PmBusError<Self::Error>> {
+                match self.numeric_format(address, command) {
+                    NumericFormat::Linear11 => Ok(Linear11::from_f64(value)?.into()),
+                    NumericFormat::Direct => {
+                        let coefficients = self.coefficients(address, command, false)$($await)* ?;
+                        Ok(Direct::from_f64(value, coefficients)?.into())
+                    }
+                }
+            }
+
+            /// Read the inventory information of the device.
+            ///
+            /// The optional commands of [`DeviceIdentity`] are `None` when the device does not acknowledge them,
+            /// and any other failure fails the whole read.
+            $($async)? fn read_identity(&mut self, address: A) -> Result<DeviceIdentity, PmBusError<Self::Error>> {
+                Ok(DeviceIdentity {
+                    manufacturer: decode_text(&self.read_mfr_id(address)$($await)* ?),
+                    model: decode_text(&self.read_mfr_model(address)$($await)* ?),
+                    revision: decode_text(&self.read_mfr_revision(address)$($await)* ?),
+                    location: optional(self.read_mfr_location(address)$($await)*)?
+                        .map(|text| decode_text(&text)),
+                    date: optional(self.read_mfr_date(address)$($await)*)?
+                        .and_then(|date| MfrDate::parse(&date)),
+                    serial: decode_text(&self.read_mfr_serial(address)$($await)* ?),
+                    ic_device_id: optional(self.read_ic_device_id(address)$($await)*)?
+                        .map(|text| decode_text(&text)),
+                    ic_device_revision: optional(self.read_ic_device_rev(address)$($await)*)?
+                        .map(|text| decode_text(&text)),
+                })
+            }
+
+            /// Write the inventory information of the device, such as during manufacturing.
+            ///
+            /// Empty or missing text and a missing date are not written, and neither are the read only IC_DEVICE_ID and IC_DEVICE_REV.
+            /// The length of every field is checked against the maximum block size before anything is written,
+            /// so that a device is not left partially provisioned. Devices may impose shorter limits of their own,
+            /// and commonly require a write protection or unlock sequence first.
+            $($async)? fn provision_identity(
+                &mut self,
+                address: A,
+                identity: &DeviceIdentity,
+            ) -> Result<(), PmBusError<Self::Error>> {
+                let date = identity.date.map(MfrDate::to_bytes);
+                let fields: [(u8, &[u8]); 6] = [
+                    (MFR_ID, identity.manufacturer.as_bytes()),
+                    (MFR_MODEL, identity.model.as_bytes()),
+                    (MFR_REVISION, identity.revision.as_bytes()),
+                    (MFR_LOCATION, identity.location.as_deref().map_or(&[], str::as_bytes)),
+                    (MFR_DATE, date.as_ref().map_or(&[], |date| date.as_slice())),
+                    (MFR_SERIAL, identity.serial.as_bytes()),
+                ];
+                let max = self.max_block_size(address);
+                if let Some((_, block)) = fields.iter().find(|(_, block)| block.len() > max) {
+                    return Err(PmBusError::BlockTooLong { len: block.len(), max });
+                }
+                for (command, block) in fields {
+                    if !block.is_empty() {
+                        self.block_write(address, command, block)$($await)* ?;
+                    }
+                }
+                Ok(())
+            }
+
+            /// Clear the flags set in `bits` from their status register, by writing them to it.
+            ///
+            /// Flags reporting conditions which are still present may be set again by the device.
+            $($async)? fn clear<S: StatusRegister>(&mut self, address: A, bits: S) -> Result<(), PmBusError<Self::Error>> {
+                if S::WORD {
+                    self.write_word(address, S::COMMAND, bits.raw())$($await)* ?;
+                } else {
+                    self.write_byte(address, S::COMMAND, bits.raw() as u8)$($await)* ?;
+                }
+                Ok(())
+            }
+
+            /// Read a [`FaultReport`] of each of `pages` with [`PmBusExt::read_fault_report`].
+            ///
+            /// The selected page is restored afterwards, even if a read fails,
+            /// in which case the first error is returned.
+            $($async)? fn read_fault_reports(
+                &mut self,
+                address: A,
+                pages: &[u8],
+                clear: bool,
+            ) -> Result<Vec<FaultReport>, PmBusError<Self::Error>> {
+                let selected = self.selected_page(address)$($await)* ?;
+                let mut reports = Vec::with_capacity(pages.len());
+                let mut result = Ok(());
+                for &page in pages {
+                    match self.read_fault_report(address, page, clear)$($await)* {
+                        Ok(report) => reports.push(report),
+                        Err(error) => {
+                            result = Err(error);
+                            break;
+                        }
+                    }
+                }
+                let restored = self.send_page(address, selected)$($await)*;
+                result.and(restored)?;
+                Ok(reports)
+            }
+
+            /// Select `page` and read its [`FaultReport`], following the summary flags of STATUS_WORD
+            /// into only the sub-status registers they indicate.
+            ///
+            /// If `clear` is set, the flags reported are then cleared from each register read.
+            /// The page is left selected.
+            $($async)? fn read_fault_report(
+                &mut self,
+                address: A,
+                page: u8,
+                clear: bool,
+            ) -> Result<FaultReport, PmBusError<Self::Error>> {
+                self.send_page(address, page)$($await)* ?;
+                let status = self.read_status_word(address)$($await)* ?;
+                let mut report = FaultReport::new(page, status);
+                if status.intersects(FaultReport::VOUT) {
+                    report.vout = Some(self.read_status_vout(address)$($await)* ?);
+                }
+                if status.intersects(FaultReport::IOUT) {
+                    report.iout = Some(self.read_status_iout(address)$($await)* ?);
+                }
+                if status.intersects(FaultReport::INPUT) {
+                    report.input = Some(self.read_status_input(address)$($await)* ?);
+                }
+                if status.intersects(StatusWord::TEMPERATURE) {
+                    report.temperature = Some(self.read_status_temperature(address)$($await)* ?);
+                }
+                if status.intersects(StatusWord::CML) {
+                    report.cml = Some(self.read_status_cml(address)$($await)* ?);
+                }
+                if status.intersects(StatusWord::OTHER) {
+                    report.other = Some(self.read_status_other(address)$($await)* ?);
+                }
+                if status.intersects(StatusWord::MFR_SPECIFIC) {
+                    report.mfr_specific = Some(self.read_status_mfr_specific(address)$($await)* ?);
+                }
+                if status.intersects(StatusWord::FANS) {
+                    report.fans_1_2 = Some(self.read_status_fans_1_2(address)$($await)* ?);
+                    report.fans_3_4 = optional(self.read_status_fans_3_4(address)$($await)*)?;
+                }
+                if clear {
+                    if let Some(bits) = report.vout {
+                        self.clear(address, bits)$($await)* ?;
+                    }
+                    if let Some(bits) = report.iout {
+                        self.clear(address, bits)$($await)* ?;
+                    }
+                    if let Some(bits) = report.input {
+                        self.clear(address, bits)$($await)* ?;
+                    }
+                    if let Some(bits) = report.temperature {
+                        self.clear(address, bits)$($await)* ?;
+                    }
+                    if let Some(bits) = report.cml {
+                        self.clear(address, bits)$($await)* ?;
+                    }
+                    if let Some(bits) = report.other {
+                        self.clear(address, bits)$($await)* ?;
+                    }
+                    if let Some(bits) = report.mfr_specific {
+                        self.send_status_mfr_specific(address, bits)$($await)* ?;
+                    }
+                    if let Some(bits) = report.fans_1_2 {
+                        self.clear(address, bits)$($await)* ?;
+                    }
+                    if let Some(bits) = report.fans_3_4 {
+                        self.clear(address, bits)$($await)* ?;
+                    }
+                    self.clear(address, status)$($await)* ?;
+                }
+                Ok(report)
+            }
+
+            /// Turn the output on with OPERATION, leaving its other bits unchanged.
+            $($async)? fn turn_on(&mut self, address: A) -> Result<(), PmBusError<Self::Error>> {
+                let operation = self.read_operation(address)$($await)* ?;
+                self.send_operation(address, Operation { on: true, ..operation })$($await)*
+            }
+
+            /// Turn the output off with OPERATION, leaving its other bits unchanged.
+            ///
+            /// The soft off bit cannot be set while on, so an output which is on is turned off immediately.
+            /// Send [`Operation::soft_off`] instead to follow TOFF_DELAY and TOFF_FALL.
+            $($async)? fn turn_off(&mut self, address: A) -> Result<(), PmBusError<Self::Error>> {
+                let operation = self.read_operation(address)$($await)* ?;
+                self.send_operation(address, Operation { on: false, ..operation })$($await)*
+            }
+
+            /// Select the output voltage `margin` with OPERATION, leaving its other bits unchanged.
+            $($async)? fn set_margin(&mut self, address: A, margin: Margin) -> Result<(), PmBusError<Self::Error>> {
+                let operation = self.read_operation(address)$($await)* ?;
+                self.send_operation(address, operation.with_margin(margin))$($await)*
+            }
+        }
+
+        impl<A: SmBusAddress, T: PmBus<A> + ?Sized> PmBusExt<A> for T {}
+    };
+}
+
+pmbus_ext_trait! {
+    /// Conversions and multi-command sequences built on the accessors of [`PmBus`].
+    ///
+    /// The generated accessors convert their data through this trait, which is implemented for every [`PmBus`].
+    /// A blocking counterpart is provided as [`blocking::PmBusExt`].
+    #[async_trait::async_trait(?Send)]
+    [async] [.await]
+}
+
+pub mod blocking {
+    use crate::error::{optional, FormatError, PmBusError};
+    use crate::format::{Coefficients, Direct, Linear11, NumericFormat, Vid, VoutMode};
+    use crate::identity::{decode_text, DeviceIdentity, MfrDate};
+    use crate::operation::{Margin, Operation};
+    use crate::smbus::SmBusAddress;
+    use crate::status::{FaultReport, StatusRegister, StatusWord};
+
+    use super::super::blocking::PmBus;
+    use super::super::{
+        MFR_DATE, MFR_ID, MFR_LOCATION, MFR_MODEL, MFR_REVISION, MFR_SERIAL, PAGE,
+        RELATIVE_VOUT_COMMANDS, VOUT_COMMAND, VOUT_MODE,
+    };
+
+    pmbus_ext_trait! {
+        /// Blocking counterpart of [`PmBusExt`](super::PmBusExt), over the blocking [`PmBus`].
+        ///
+        /// The provided methods are generated from the same source as those of the asynchronous trait.
+        [] []
+    }
+}
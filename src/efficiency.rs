@@ -0,0 +1,75 @@
+//! Efficiency data read by MFR_EFFICIENCY_LL and MFR_EFFICIENCY_HL.
+//!
+//! The device reports its efficiency at three loads, for a low line or high line input voltage.
+//! [`EfficiencyData::efficiency_at`] estimates the efficiency at other loads between them.
+
+use crate::format::Linear11;
+use crate::units::{Percent, Volts, Watts};
+
+/// The efficiency at one output power.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EfficiencyPoint {
+    pub output_power: Watts,
+    pub efficiency: Percent,
+}
+
+/// The response to MFR_EFFICIENCY_LL or MFR_EFFICIENCY_HL, laid out as seven Linear11 words:
+/// the input voltage, then the output power and efficiency at a light, medium and high load.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EfficiencyData {
+    pub input_voltage: Volts,
+    /// The efficiency at a light, medium and high load, in that order.
+    pub points: [EfficiencyPoint; 3],
+}
+
+impl EfficiencyData {
+    /// Estimate the efficiency at `output_power`, by linear interpolation between the nearest points.
+    ///
+    /// Below the lightest or above the heaviest load, the efficiency of that point is returned,
+    /// as extrapolating efficiency curves is unreliable.
+    pub fn efficiency_at(&self, output_power: Watts) -> Percent {
+        let mut points = self.points;
+        points.sort_by(|a, b| a.output_power.0.total_cmp(&b.output_power.0));
+        let [lightest, .., heaviest] = points;
+        if output_power <= lightest.output_power {
+            return lightest.efficiency;
+        }
+        if output_power >= heaviest.output_power {
+            return heaviest.efficiency;
+        }
+        let (below, above) = points
+            .windows(2)
+            .map(|pair| (pair[0], pair[1]))
+            .find(|(_, above)| output_power <= above.output_power)
+            .unwrap_or((lightest, heaviest));
+        let span = above.output_power.0 - below.output_power.0;
+        if span <= 0.0 {
+            return above.efficiency;
+        }
+        let fraction = (output_power.0 - below.output_power.0) / span;
+        Percent(below.efficiency.0 + fraction * (above.efficiency.0 - below.efficiency.0))
+    }
+
+    /// The input power drawn when delivering `output_power`, estimated by [`EfficiencyData::efficiency_at`].
+    ///
+    /// Returns `None` if the efficiency is not positive.
+    pub fn input_power_at(&self, output_power: Watts) -> Option<Watts> {
+        let efficiency = self.efficiency_at(output_power).0;
+        (efficiency > 0.0).then(|| Watts(output_power.0 * 100.0 / efficiency))
+    }
+}
+
+impl From<[u8; 14]> for EfficiencyData {
+    fn from(bytes: [u8; 14]) -> Self {
+        let word =
+            |index: usize| Linear11(u16::from_le_bytes([bytes[index], bytes[index + 1]])).to_f64();
+        let point = |index: usize| EfficiencyPoint {
+            output_power: Watts(word(index)),
+            efficiency: Percent(word(index + 2)),
+        };
+        Self {
+            input_voltage: Volts(word(0)),
+            points: [point(2), point(6), point(10)],
+        }
+    }
+}
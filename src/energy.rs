@@ -0,0 +1,290 @@
+//! Energy accumulators read by READ_EIN and READ_EOUT, and the kilowatt-hour counters read by READ_KWH_IN and READ_KWH_OUT.
+//!
+//! The device adds a power sample to the accumulator at a fixed rate, counting the samples taken.
+//! Dividing the energy accumulated between two reads by the number of samples taken
+//! gives the average power over the interval, see [`EnergyMeter`].
+
+use crate::error::FormatError;
+use crate::format::Coefficients;
+use crate::units::{Joules, Watts};
+
+/// The number of distinct values of the 24-bit sample count.
+const SAMPLE_COUNT_MODULUS: u32 = 1 << 24;
+
+/// The number of distinct values of the rollover count.
+const ROLLOVER_COUNT_MODULUS: u32 = 256;
+
+const JOULES_PER_KWH: f64 = 3.6e6;
+
+/// The response to READ_EIN or READ_EOUT, laid out as
+/// `[accumulator_lo, accumulator_hi, rollover_count, sample_count_lo, sample_count_mid, sample_count_hi]`.
+///
+/// The accumulator is a raw DIRECT value, which is only converted to power once averaged, see [`EnergyMeter`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct EnergyCount {
+    /// The raw energy accumulated since the last rollover, which rolls over at [`EnergyCount::ACCUMULATOR_ROLLOVER`].
+    pub accumulator: u16,
+    /// The number of times the accumulator has rolled over, which itself wraps at 256.
+    pub rollover_count: u8,
+    /// The number of samples taken, which wraps at 2^24.
+    pub sample_count: u32,
+}
+
+impl EnergyCount {
+    /// The raw value at which the accumulator rolls over to zero, one past the largest positive DIRECT value.
+    pub const ACCUMULATOR_ROLLOVER: u32 = 0x8000;
+
+    /// The raw energy accumulated, including rollovers, which wraps along with the rollover count.
+    pub const fn total(self) -> u32 {
+        self.rollover_count as u32 * Self::ACCUMULATOR_ROLLOVER + self.accumulator as u32
+    }
+}
+
+impl From<[u8; 6]> for EnergyCount {
+    fn from(bytes: [u8; 6]) -> Self {
+        Self {
+            accumulator: u16::from_le_bytes([bytes[0], bytes[1]]),
+            rollover_count: bytes[2],
+            sample_count: u32::from_le_bytes([bytes[3], bytes[4], bytes[5], 0]),
+        }
+    }
+}
+
+impl From<EnergyCount> for [u8; 6] {
+    fn from(count: EnergyCount) -> Self {
+        let [accumulator_lo, accumulator_hi] = count.accumulator.to_le_bytes();
+        let [sample_lo, sample_mid, sample_hi, _] = count.sample_count.to_le_bytes();
+        [
+            accumulator_lo,
+            accumulator_hi,
+            count.rollover_count,
+            sample_lo,
+            sample_mid,
+            sample_hi,
+        ]
+    }
+}
+
+/// Computes the average power between successive [`EnergyCount`]s of a device.
+///
+/// The average raw sample is decoded with the DIRECT coefficients of the command,
+/// such as those read by [`PmBusExt::coefficients`](crate::commands::PmBusExt::coefficients).
+///
+/// The rollover and sample counts may each wrap at most once between samples,
+/// so they must be read often enough for that to hold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnergyMeter {
+    coefficients: Coefficients,
+    last: Option<EnergyCount>,
+}
+
+impl EnergyMeter {
+    /// Fails with [`FormatError::Invalid`] if `m` of the `coefficients` is zero.
+    pub fn new(coefficients: Coefficients) -> Result<Self, FormatError> {
+        if coefficients.m == 0 {
+            return Err(FormatError::Invalid);
+        }
+        Ok(Self {
+            coefficients,
+            last: None,
+        })
+    }
+
+    /// The last sample given to [`EnergyMeter::sample`].
+    pub fn last(&self) -> Option<EnergyCount> {
+        self.last
+    }
+
+    /// Record `count`, returning the average power since the previous sample.
+    ///
+    /// Returns `None` for the first sample, or if no samples have been taken by the device since.
+    pub fn sample(&mut self, count: EnergyCount) -> Option<Watts> {
+        let average = self
+            .last
+            .and_then(|last| Self::average_raw(last, count))
+            .map(|raw| self.decode(raw));
+        self.last = Some(count);
+        average
+    }
+
+    /// The average raw power sample between the samples `from` and `to`, before decoding.
+    ///
+    /// Returns `None` if no samples have been taken by the device in between.
+    pub fn average_raw(from: EnergyCount, to: EnergyCount) -> Option<f64> {
+        let samples = to.sample_count.wrapping_sub(from.sample_count) % SAMPLE_COUNT_MODULUS;
+        if samples == 0 {
+            return None;
+        }
+        // The total wraps along with the rollover count.
+        let modulus = ROLLOVER_COUNT_MODULUS * EnergyCount::ACCUMULATOR_ROLLOVER;
+        let energy = (to.total() + modulus - from.total()) % modulus;
+        Some(energy as f64 / samples as f64)
+    }
+
+    /// Decode an average raw sample as `X = (Y·10^-R - b) / m`.
+    fn decode(&self, raw: f64) -> Watts {
+        let Coefficients { m, b, r } = self.coefficients;
+        Watts((raw * 10f64.powi(-(r as i32)) - b as f64) / m as f64)
+    }
+}
+
+/// A 32-bit kilowatt-hour counter read by READ_KWH_IN or READ_KWH_OUT, which wraps to zero.
+///
+/// See [`KwhTotal`] to extend the counter across wraparound.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KwhCounter(pub u32);
+
+impl KwhCounter {
+    /// The energy counted, in kilowatt-hours.
+    pub const fn kwh(self) -> u32 {
+        self.0
+    }
+
+    pub fn to_joules(self) -> Joules {
+        Joules(self.0 as f64 * JOULES_PER_KWH)
+    }
+}
+
+impl From<u32> for KwhCounter {
+    fn from(raw: u32) -> Self {
+        Self(raw)
+    }
+}
+
+impl From<KwhCounter> for u32 {
+    fn from(counter: KwhCounter) -> Self {
+        counter.0
+    }
+}
+
+/// One of the two kilowatt-hour counters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KwhDirection {
+    /// READ_KWH_IN, counting input energy.
+    In,
+    /// READ_KWH_OUT, counting output energy.
+    Out,
+}
+
+/// The value of READ_KWH_CONFIG, which controls the kilowatt-hour counters.
+///
+/// The low byte controls READ_KWH_IN and the high byte READ_KWH_OUT, each with an enable and a reset bit.
+/// The remaining bits are reserved, and are kept as they were read,
+/// so that a value read, modified and written back leaves them unchanged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct KwhConfig(pub u16);
+
+impl KwhConfig {
+    /// Counting of input energy is enabled.
+    pub const ENABLE_IN: u16 = 1 << 0;
+    /// Reset READ_KWH_IN to zero when written, which the device clears once done.
+    pub const RESET_IN: u16 = 1 << 1;
+    /// Counting of output energy is enabled.
+    pub const ENABLE_OUT: u16 = 1 << 8;
+    /// Reset READ_KWH_OUT to zero when written, which the device clears once done.
+    pub const RESET_OUT: u16 = 1 << 9;
+    /// Every bit with a meaning.
+    pub const DEFINED: u16 = Self::ENABLE_IN | Self::RESET_IN | Self::ENABLE_OUT | Self::RESET_OUT;
+
+    pub const fn enabled(self, direction: KwhDirection) -> bool {
+        self.0 & Self::enable_bit(direction) != 0
+    }
+
+    pub const fn with_enabled(self, direction: KwhDirection, enabled: bool) -> Self {
+        self.with_bit(Self::enable_bit(direction), enabled)
+    }
+
+    pub const fn reset(self, direction: KwhDirection) -> bool {
+        self.0 & Self::reset_bit(direction) != 0
+    }
+
+    /// Request (or cancel a request) that the counter is reset when the configuration is written.
+    pub const fn with_reset(self, direction: KwhDirection, reset: bool) -> Self {
+        self.with_bit(Self::reset_bit(direction), reset)
+    }
+
+    /// The reserved bits, outside of [`KwhConfig::DEFINED`].
+    pub const fn reserved_bits(self) -> u16 {
+        self.0 & !Self::DEFINED
+    }
+
+    const fn enable_bit(direction: KwhDirection) -> u16 {
+        match direction {
+            KwhDirection::In => Self::ENABLE_IN,
+            KwhDirection::Out => Self::ENABLE_OUT,
+        }
+    }
+
+    const fn reset_bit(direction: KwhDirection) -> u16 {
+        match direction {
+            KwhDirection::In => Self::RESET_IN,
+            KwhDirection::Out => Self::RESET_OUT,
+        }
+    }
+
+    const fn with_bit(self, bit: u16, set: bool) -> Self {
+        if set {
+            Self(self.0 | bit)
+        } else {
+            Self(self.0 & !bit)
+        }
+    }
+}
+
+impl From<u16> for KwhConfig {
+    fn from(raw: u16) -> Self {
+        Self(raw)
+    }
+}
+
+impl From<KwhConfig> for u16 {
+    fn from(config: KwhConfig) -> Self {
+        config.0
+    }
+}
+
+/// Extends a [`KwhCounter`] to 64 bits, by counting the times it wraps around.
+///
+/// The counter must be updated at least once per wraparound, which is not a concern in practice.
+/// The [`total`](KwhTotal::total) can be stored, and used to [`resume`](KwhTotal::resume) counting later,
+/// such as after a restart of the host.
+///
+/// A counter which goes backwards is assumed to have wrapped around,
+/// so this must not be used across a reset of the counter by [`KwhConfig::with_reset`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct KwhTotal {
+    total: Option<u64>,
+}
+
+impl KwhTotal {
+    pub const fn new() -> Self {
+        Self { total: None }
+    }
+
+    /// Continue from a `total` previously returned by [`KwhTotal::total`].
+    pub const fn resume(total: u64) -> Self {
+        Self { total: Some(total) }
+    }
+
+    /// The total energy counted in kilowatt-hours, or `None` before the first update.
+    pub const fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    /// Record a reading of the counter, returning the new total in kilowatt-hours.
+    pub fn update(&mut self, counter: KwhCounter) -> u64 {
+        let counter = counter.0 as u64;
+        let total = match self.total {
+            None => counter,
+            Some(total) => {
+                let mut wraps = total >> 32;
+                if counter < total & 0xFFFF_FFFF {
+                    wraps += 1;
+                }
+                wraps << 32 | counter
+            }
+        };
+        self.total = Some(total);
+        total
+    }
+}
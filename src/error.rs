@@ -0,0 +1,216 @@
+use embedded_hal::i2c::{Error, ErrorKind, NoAcknowledgeSource};
+
+/// Errors produced by [`SmBus`](crate::smbus::SmBus) transactions.
+///
+/// The type parameter `E` is the error type of the underlying I2C bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmBusError<E> {
+    /// The underlying I2C bus reported an error.
+    Bus(E),
+    /// A block is longer than the maximum block size permitted on the bus.
+    ///
+    /// This is returned both for blocks given to a write and for byte counts reported by a device.
+    BlockTooLong { len: usize, max: usize },
+    /// The number of bytes received does not match the number of bytes expected.
+    ByteCountMismatch { expected: usize, received: usize },
+    /// The Packet Error Code received from the device does not match the one computed by the host.
+    ///
+    /// 6.4, Pg. 34
+    Pec { expected: u8, received: u8 },
+    /// The command is not supported by the device, or by this implementation.
+    Unsupported { command: u8 },
+}
+
+impl<E> From<E> for SmBusError<E> {
+    fn from(error: E) -> Self {
+        Self::Bus(error)
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for SmBusError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Bus(error) => write!(f, "bus error: {error}"),
+            Self::BlockTooLong { len, max } => {
+                write!(f, "block of {len} bytes exceeds the maximum of {max} bytes")
+            }
+            Self::ByteCountMismatch { expected, received } => {
+                write!(f, "expected {expected} bytes, received {received} bytes")
+            }
+            Self::Pec { expected, received } => write!(
+                f,
+                "packet error code mismatch (expected {expected:#04X}, received {received:#04X})"
+            ),
+            Self::Unsupported { command } => write!(f, "unsupported command {command:#04X}"),
+        }
+    }
+}
+
+impl<E: core::fmt::Debug + core::fmt::Display> std::error::Error for SmBusError<E> {}
+
+impl<E: Error> Error for SmBusError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Bus(error) => error.kind(),
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+/// Errors produced by [`PmBus`](crate::commands::PmBus) commands.
+///
+/// This mirrors [`SmBusError`], which converts into it losslessly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PmBusError<E> {
+    /// The underlying I2C bus reported an error.
+    Bus(E),
+    /// See [`SmBusError::BlockTooLong`].
+    BlockTooLong { len: usize, max: usize },
+    /// See [`SmBusError::ByteCountMismatch`].
+    ByteCountMismatch { expected: usize, received: usize },
+    /// See [`SmBusError::Pec`].
+    Pec { expected: u8, received: u8 },
+    /// See [`SmBusError::Unsupported`].
+    Unsupported { command: u8 },
+    /// The data could not be converted to or from its format, such as a numeric format or a bit-field.
+    Format(FormatError),
+}
+
+impl<E> From<SmBusError<E>> for PmBusError<E> {
+    fn from(error: SmBusError<E>) -> Self {
+        match error {
+            SmBusError::Bus(error) => Self::Bus(error),
+            SmBusError::BlockTooLong { len, max } => Self::BlockTooLong { len, max },
+            SmBusError::ByteCountMismatch { expected, received } => {
+                Self::ByteCountMismatch { expected, received }
+            }
+            SmBusError::Pec { expected, received } => Self::Pec { expected, received },
+            SmBusError::Unsupported { command } => Self::Unsupported { command },
+        }
+    }
+}
+
+/// Allows infallible conversions of the data read by a command to be treated like fallible ones.
+impl<E> From<core::convert::Infallible> for PmBusError<E> {
+    fn from(infallible: core::convert::Infallible) -> Self {
+        match infallible {}
+    }
+}
+
+impl<E> From<FormatError> for PmBusError<E> {
+    fn from(error: FormatError) -> Self {
+        Self::Format(error)
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for PmBusError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Bus(error) => write!(f, "bus error: {error}"),
+            Self::BlockTooLong { len, max } => {
+                write!(f, "block of {len} bytes exceeds the maximum of {max} bytes")
+            }
+            Self::ByteCountMismatch { expected, received } => {
+                write!(f, "expected {expected} bytes, received {received} bytes")
+            }
+            Self::Pec { expected, received } => write!(
+                f,
+                "packet error code mismatch (expected {expected:#04X}, received {received:#04X})"
+            ),
+            Self::Unsupported { command } => write!(f, "unsupported command {command:#04X}"),
+            Self::Format(error) => error.fmt(f),
+        }
+    }
+}
+
+impl<E: core::fmt::Debug + core::fmt::Display> std::error::Error for PmBusError<E> {}
+
+impl<E: Error> PmBusError<E> {
+    /// Whether the device refused the command as unsupported, by not acknowledging it or its data.
+    ///
+    /// A device which does not acknowledge its address is missing rather than refusing the command,
+    /// so that is not included. Neither is a NACK of unknown source, as many buses report every NACK that way.
+    pub fn is_unsupported(&self) -> bool {
+        match self {
+            Self::Bus(error) => error.kind() == ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+            Self::Unsupported { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+/// Treat a read refused by the device as unsupported as `None`, for commands which are optional.
+pub(crate) fn optional<T, E: Error>(
+    result: Result<T, PmBusError<E>>,
+) -> Result<Option<T>, PmBusError<E>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(error) if error.is_unsupported() => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+impl<E: Error> Error for PmBusError<E> {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Bus(error) => error.kind(),
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+/// Errors produced while waiting for and servicing `SMBALERT#`.
+///
+/// The type parameters `E` and `P` are the error types of the I2C bus and the alert pin respectively.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertError<E, P> {
+    /// A transaction on the bus failed.
+    Bus(SmBusError<E>),
+    /// The alert pin reported an error.
+    Pin(P),
+}
+
+impl<E: core::fmt::Display, P: core::fmt::Display> core::fmt::Display for AlertError<E, P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Bus(error) => error.fmt(f),
+            Self::Pin(error) => write!(f, "alert pin error: {error}"),
+        }
+    }
+}
+
+impl<E, P> std::error::Error for AlertError<E, P>
+where
+    E: core::fmt::Debug + core::fmt::Display,
+    P: core::fmt::Debug + core::fmt::Display,
+{
+}
+
+/// Errors produced when converting a value to or from one of the numeric [`format`](crate::format)s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormatError {
+    /// The value is infinite or NaN.
+    NotFinite,
+    /// The value is too large in magnitude to be represented, or the raw data has no value in the format.
+    OutOfRange,
+    /// The data format selected by the device, such as by VOUT_MODE, is not supported by the conversion.
+    Unsupported,
+    /// The raw data uses a combination of bits which is reserved by the specification.
+    Reserved,
+    /// The value can be represented, but is refused as it is not meaningful for the command.
+    Invalid,
+}
+
+impl core::fmt::Display for FormatError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotFinite => write!(f, "value is not finite"),
+            Self::OutOfRange => write!(f, "value is out of range"),
+            Self::Unsupported => write!(f, "unsupported data format"),
+            Self::Reserved => write!(f, "reserved bits or combination of bits"),
+            Self::Invalid => write!(f, "invalid value"),
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
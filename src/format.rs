@@ -0,0 +1,31 @@
+//! Numeric data formats used by PMBus commands.
+//!
+//! Part II, 7
+
+pub mod direct;
+pub mod half;
+pub mod linear11;
+pub mod linear16;
+pub mod vid;
+pub mod vout_mode;
+
+pub use self::direct::{Coefficients, Direct};
+pub use self::half::Half;
+pub use self::linear11::Linear11;
+pub use self::linear16::Linear16;
+pub use self::vid::{Vid, VidCode};
+pub use self::vout_mode::VoutMode;
+
+/// The format of numeric commands other than output voltages, which is the same for every command of a device.
+///
+/// Part II, 7.1
+///
+/// See [`PmBus::numeric_format`](crate::commands::PmBus::numeric_format).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum NumericFormat {
+    /// See [`Linear11`].
+    #[default]
+    Linear11,
+    /// See [`Direct`], using the coefficients of each command.
+    Direct,
+}
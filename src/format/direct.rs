@@ -0,0 +1,100 @@
+use crate::error::FormatError;
+
+/// Coefficients of the DIRECT data format, which are specific to each command.
+///
+/// Part II, 7.4
+///
+/// These are returned by the COEFFICIENTS command, or may be given in a device datasheet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Coefficients {
+    /// The slope coefficient, which must not be zero.
+    pub m: i16,
+    /// The offset.
+    pub b: i16,
+    /// The exponent.
+    pub r: i8,
+}
+
+impl Coefficients {
+    pub const fn new(m: i16, b: i16, r: i8) -> Self {
+        Self { m, b, r }
+    }
+}
+
+/// Decode the response to COEFFICIENTS, laid out as `[m_lo, m_hi, b_lo, b_hi, r]`.
+impl From<[u8; 5]> for Coefficients {
+    fn from(bytes: [u8; 5]) -> Self {
+        Self {
+            m: i16::from_le_bytes([bytes[0], bytes[1]]),
+            b: i16::from_le_bytes([bytes[2], bytes[3]]),
+            r: bytes[4] as i8,
+        }
+    }
+}
+
+impl From<Coefficients> for [u8; 5] {
+    fn from(coefficients: Coefficients) -> Self {
+        let [m_lo, m_hi] = coefficients.m.to_le_bytes();
+        let [b_lo, b_hi] = coefficients.b.to_le_bytes();
+        [m_lo, m_hi, b_lo, b_hi, coefficients.r as u8]
+    }
+}
+
+/// DIRECT data format, a sixteen-bit two's complement value `Y`,
+/// representing the value `X = (Y·10^-R - b) / m` with the [`Coefficients`] of the command.
+///
+/// Part II, 7.4
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Direct(pub u16);
+
+impl Direct {
+    /// Decode the value, failing with [`FormatError::Invalid`] if `m` is zero.
+    pub fn to_f64(self, coefficients: Coefficients) -> Result<f64, FormatError> {
+        let Coefficients { m, b, r } = coefficients;
+        if m == 0 {
+            return Err(FormatError::Invalid);
+        }
+        Ok((self.0 as i16 as f64 * 10f64.powi(-(r as i32)) - b as f64) / m as f64)
+    }
+
+    /// See [`Direct::to_f64`].
+    pub fn to_f32(self, coefficients: Coefficients) -> Result<f32, FormatError> {
+        self.to_f64(coefficients).map(|value| value as f32)
+    }
+
+    /// Encode `value` as `Y = (m·X + b)·10^R`, rounding to the nearest integer.
+    ///
+    /// Fails with [`FormatError::Invalid`] if `m` is zero, as no value could then be decoded.
+    pub fn from_f64(value: f64, coefficients: Coefficients) -> Result<Self, FormatError> {
+        if !value.is_finite() {
+            return Err(FormatError::NotFinite);
+        }
+        let Coefficients { m, b, r } = coefficients;
+        if m == 0 {
+            return Err(FormatError::Invalid);
+        }
+        let y = ((m as f64 * value + b as f64) * 10f64.powi(r as i32)).round();
+        if (i16::MIN as f64..=i16::MAX as f64).contains(&y) {
+            Ok(Self(y as i16 as u16))
+        } else {
+            Err(FormatError::OutOfRange)
+        }
+    }
+
+    /// See [`Direct::from_f64`].
+    pub fn from_f32(value: f32, coefficients: Coefficients) -> Result<Self, FormatError> {
+        Self::from_f64(value as f64, coefficients)
+    }
+}
+
+impl From<u16> for Direct {
+    fn from(raw: u16) -> Self {
+        Self(raw)
+    }
+}
+
+impl From<Direct> for u16 {
+    fn from(value: Direct) -> Self {
+        value.0
+    }
+}
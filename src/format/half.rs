@@ -0,0 +1,123 @@
+use crate::error::FormatError;
+
+/// IEEE 754 half-precision (binary16) floating point, with a sign bit,
+/// a five-bit biased exponent and a ten-bit mantissa.
+///
+/// This is the format of output voltages when [`VoutMode::Half`](super::VoutMode::Half) is selected.
+///
+/// Part II, 8.3
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Half(pub u16);
+
+impl Half {
+    pub const INFINITY: Self = Self(0x7C00);
+    pub const NEG_INFINITY: Self = Self(0xFC00);
+    /// The largest finite value, `65504`.
+    pub const MAX: Self = Self(0x7BFF);
+    /// The smallest finite value, `-65504`.
+    pub const MIN: Self = Self(0xFBFF);
+
+    pub fn to_f64(self) -> f64 {
+        let sign = if self.0 & 0x8000 == 0 { 1.0 } else { -1.0 };
+        let exponent = ((self.0 >> 10) & 0x1F) as i32;
+        let mantissa = (self.0 & 0x3FF) as f64;
+        match exponent {
+            // Zero and subnormals, without the implicit leading one.
+            0 => sign * mantissa * 2f64.powi(-24),
+            0x1F if mantissa == 0.0 => sign * f64::INFINITY,
+            0x1F => f64::NAN,
+            _ => sign * (1024.0 + mantissa) * 2f64.powi(exponent - 25),
+        }
+    }
+
+    pub fn to_f32(self) -> f32 {
+        self.to_f64() as f32
+    }
+
+    /// Encode `value`, rounding to the nearest representable value (ties to even).
+    ///
+    /// Values too large in magnitude are out of range rather than infinite,
+    /// and values too small in magnitude become zero.
+    pub fn from_f64(value: f64) -> Result<Self, FormatError> {
+        if !value.is_finite() {
+            return Err(FormatError::NotFinite);
+        }
+        let bits = value.to_bits();
+        let sign = ((bits >> 48) & 0x8000) as u16;
+        let exponent = ((bits >> 52) & 0x7FF) as i32 - 1023;
+        if exponent < -14 {
+            // Subnormal, scaling to units of the smallest subnormal is exact.
+            let scaled = value.abs() * 2f64.powi(24);
+            let mut mantissa = scaled.round();
+            if scaled - scaled.floor() == 0.5 && mantissa % 2.0 == 1.0 {
+                mantissa -= 1.0;
+            }
+            // A mantissa rounded up to 1024 is the smallest normal value, which has the same encoding.
+            return Ok(Self(sign | mantissa as u16));
+        }
+        if exponent > 15 {
+            return Err(FormatError::OutOfRange);
+        }
+        // Round the 52-bit mantissa to 10 bits.
+        let fraction = bits & ((1 << 52) - 1);
+        let remainder = fraction & ((1 << 42) - 1);
+        let mut mantissa = (fraction >> 42) as u16;
+        let mut exponent = (exponent + 15) as u16;
+        if remainder > 1 << 41 || (remainder == 1 << 41 && mantissa & 1 == 1) {
+            mantissa += 1;
+            if mantissa == 1 << 10 {
+                mantissa = 0;
+                exponent += 1;
+            }
+        }
+        if exponent >= 0x1F {
+            return Err(FormatError::OutOfRange);
+        }
+        Ok(Self(sign | exponent << 10 | mantissa))
+    }
+
+    /// See [`Half::from_f64`].
+    pub fn from_f32(value: f32) -> Result<Self, FormatError> {
+        Self::from_f64(value as f64)
+    }
+}
+
+impl From<u16> for Half {
+    fn from(raw: u16) -> Self {
+        Self(raw)
+    }
+}
+
+impl From<Half> for u16 {
+    fn from(value: Half) -> Self {
+        value.0
+    }
+}
+
+impl From<Half> for f64 {
+    fn from(value: Half) -> Self {
+        value.to_f64()
+    }
+}
+
+impl From<Half> for f32 {
+    fn from(value: Half) -> Self {
+        value.to_f32()
+    }
+}
+
+impl TryFrom<f64> for Half {
+    type Error = FormatError;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Self::from_f64(value)
+    }
+}
+
+impl TryFrom<f32> for Half {
+    type Error = FormatError;
+
+    fn try_from(value: f32) -> Result<Self, Self::Error> {
+        Self::from_f32(value)
+    }
+}
@@ -0,0 +1,121 @@
+use crate::error::FormatError;
+
+/// Smallest and largest exponents of the five-bit two's complement exponent.
+const EXPONENT_MIN: i8 = -16;
+const EXPONENT_MAX: i8 = 15;
+
+/// Smallest and largest values of the eleven-bit two's complement mantissa.
+const MANTISSA_MIN: i16 = -1024;
+const MANTISSA_MAX: i16 = 1023;
+
+/// LINEAR11 data format, a five-bit exponent `N` and an eleven-bit mantissa `Y` in a single word,
+/// representing the value `Y·2^N`.
+///
+/// Part II, 7.1
+///
+/// The raw word is kept as-is, so that converting to and from [`u16`] is lossless.
+/// Decoding is exact for both [`f32`] and [`f64`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Linear11(pub u16);
+
+impl Linear11 {
+    /// The largest value which can be encoded, `1023·2^15`.
+    pub const MAX: f64 = MANTISSA_MAX as f64 * (1 << EXPONENT_MAX) as f64;
+    /// The smallest (most negative) value which can be encoded, `-1024·2^15`.
+    pub const MIN: f64 = MANTISSA_MIN as f64 * (1 << EXPONENT_MAX) as f64;
+
+    /// Pack an exponent and mantissa, or `None` if either does not fit in its field.
+    pub const fn new(exponent: i8, mantissa: i16) -> Option<Self> {
+        if exponent < EXPONENT_MIN
+            || exponent > EXPONENT_MAX
+            || mantissa < MANTISSA_MIN
+            || mantissa > MANTISSA_MAX
+        {
+            return None;
+        }
+        Some(Self(
+            ((exponent as u16 & 0x1F) << 11) | (mantissa as u16 & 0x07FF),
+        ))
+    }
+
+    /// The exponent `N`, sign-extended from bits 15:11.
+    pub const fn exponent(self) -> i8 {
+        (self.0 as i16 >> 11) as i8
+    }
+
+    /// The mantissa `Y`, sign-extended from bits 10:0.
+    pub const fn mantissa(self) -> i16 {
+        ((self.0 << 5) as i16) >> 5
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.mantissa() as f64 * 2f64.powi(self.exponent() as i32)
+    }
+
+    pub fn to_f32(self) -> f32 {
+        self.mantissa() as f32 * 2f32.powi(self.exponent() as i32)
+    }
+
+    /// Encode `value`, rounding to the nearest value representable with the smallest possible exponent,
+    /// which is the one with the greatest precision.
+    pub fn from_f64(value: f64) -> Result<Self, FormatError> {
+        if !value.is_finite() {
+            return Err(FormatError::NotFinite);
+        }
+        (EXPONENT_MIN..=EXPONENT_MAX)
+            .find_map(|exponent| {
+                let mantissa = (value * 2f64.powi(-(exponent as i32))).round();
+                if (MANTISSA_MIN as f64..=MANTISSA_MAX as f64).contains(&mantissa) {
+                    Self::new(exponent, mantissa as i16)
+                } else {
+                    None
+                }
+            })
+            .ok_or(FormatError::OutOfRange)
+    }
+
+    /// See [`Linear11::from_f64`].
+    pub fn from_f32(value: f32) -> Result<Self, FormatError> {
+        Self::from_f64(value as f64)
+    }
+}
+
+impl From<u16> for Linear11 {
+    fn from(raw: u16) -> Self {
+        Self(raw)
+    }
+}
+
+impl From<Linear11> for u16 {
+    fn from(value: Linear11) -> Self {
+        value.0
+    }
+}
+
+impl From<Linear11> for f64 {
+    fn from(value: Linear11) -> Self {
+        value.to_f64()
+    }
+}
+
+impl From<Linear11> for f32 {
+    fn from(value: Linear11) -> Self {
+        value.to_f32()
+    }
+}
+
+impl TryFrom<f64> for Linear11 {
+    type Error = FormatError;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Self::from_f64(value)
+    }
+}
+
+impl TryFrom<f32> for Linear11 {
+    type Error = FormatError;
+
+    fn try_from(value: f32) -> Result<Self, Self::Error> {
+        Self::from_f32(value)
+    }
+}
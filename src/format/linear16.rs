@@ -0,0 +1,51 @@
+use crate::error::FormatError;
+
+/// ULINEAR16 data format, an unsigned sixteen-bit mantissa `V` scaled by an exponent `N` held elsewhere,
+/// representing the value `V·2^N`.
+///
+/// Part II, 8.3.1
+///
+/// This is the format of output voltages when [`VoutMode::Linear`](super::VoutMode::Linear) is selected,
+/// which provides the exponent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Linear16(pub u16);
+
+impl Linear16 {
+    pub fn to_f64(self, exponent: i8) -> f64 {
+        self.0 as f64 * 2f64.powi(exponent as i32)
+    }
+
+    pub fn to_f32(self, exponent: i8) -> f32 {
+        self.0 as f32 * 2f32.powi(exponent as i32)
+    }
+
+    /// Encode `value` with `exponent`, rounding to the nearest representable value.
+    pub fn from_f64(value: f64, exponent: i8) -> Result<Self, FormatError> {
+        if !value.is_finite() {
+            return Err(FormatError::NotFinite);
+        }
+        let mantissa = (value * 2f64.powi(-(exponent as i32))).round();
+        if (0.0..=u16::MAX as f64).contains(&mantissa) {
+            Ok(Self(mantissa as u16))
+        } else {
+            Err(FormatError::OutOfRange)
+        }
+    }
+
+    /// See [`Linear16::from_f64`].
+    pub fn from_f32(value: f32, exponent: i8) -> Result<Self, FormatError> {
+        Self::from_f64(value as f64, exponent)
+    }
+}
+
+impl From<u16> for Linear16 {
+    fn from(raw: u16) -> Self {
+        Self(raw)
+    }
+}
+
+impl From<Linear16> for u16 {
+    fn from(value: Linear16) -> Self {
+        value.0
+    }
+}
@@ -0,0 +1,141 @@
+use crate::error::FormatError;
+
+/// A VID code table, selected by the VID code type in the parameter of VOUT_MODE.
+///
+/// Part II, 8.3
+///
+/// The numbering of code types is not defined by this crate, and is taken from the datasheet of each device
+/// through [`PmBus::vid_table`](crate::commands::PmBus::vid_table).
+/// Tables mapping the code to a voltage offset and step are given in millivolts below;
+/// codes not listed are out of range, and code `0` turns the output off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VidCode {
+    /// VR11.0, `1612.5 - 6.25·code` for codes `0x02..=0xB2` (1.6 V down to 0.5 V).
+    /// Codes `0x01`, `0xFE` and `0xFF` also turn the output off.
+    Vr11,
+    /// VR12.0, `250 + 5·(code - 1)` for codes `0x01..=0xFF`.
+    Vr12,
+    /// VR12.5, `500 + 10·(code - 1)` for codes `0x01..=0xFF`.
+    Vr12_5,
+    /// VR13.0 with a 5 mV DAC, `250 + 5·(code - 1)` for codes `0x01..=0xFF`.
+    Vr13Step5mV,
+    /// VR13.0 with a 10 mV DAC, `500 + 10·(code - 1)` for codes `0x01..=0xFF`.
+    Vr13Step10mV,
+    /// VR14, `250 + 5·(code - 1)` for codes `0x01..=0xFF`.
+    Vr14,
+    /// IMVP8, `250 + 5·(code - 1)` for codes `0x01..=0xFF`.
+    Imvp8,
+    /// IMVP9, `200 + 10·(code - 1)` for codes `0x01..=0xFF`.
+    Imvp9,
+}
+
+impl VidCode {
+    pub const ALL: [Self; 8] = [
+        Self::Vr11,
+        Self::Vr12,
+        Self::Vr12_5,
+        Self::Vr13Step5mV,
+        Self::Vr13Step10mV,
+        Self::Vr14,
+        Self::Imvp8,
+        Self::Imvp9,
+    ];
+
+    /// The voltage of a code in millivolts, or `None` if the code is out of range or turns the output off.
+    pub fn millivolts(self, code: u8) -> Option<f64> {
+        let (first, last) = self.codes();
+        if !(first..=last).contains(&code) {
+            return None;
+        }
+        let (offset, step) = self.line();
+        Some(offset + step * code as f64)
+    }
+
+    /// The first and last codes which have a voltage.
+    fn codes(self) -> (u8, u8) {
+        match self {
+            Self::Vr11 => (0x02, 0xB2),
+            _ => (0x01, 0xFF),
+        }
+    }
+
+    /// The voltage of code `0` (by extrapolation) and the step per code, in millivolts.
+    fn line(self) -> (f64, f64) {
+        match self {
+            Self::Vr11 => (1612.5, -6.25),
+            Self::Vr12 | Self::Vr13Step5mV | Self::Vr14 | Self::Imvp8 => (245.0, 5.0),
+            Self::Vr12_5 | Self::Vr13Step10mV => (490.0, 10.0),
+            Self::Imvp9 => (190.0, 10.0),
+        }
+    }
+
+    /// Whether the code turns the output off.
+    pub fn is_off(self, code: u8) -> bool {
+        match self {
+            Self::Vr11 => matches!(code, 0x00 | 0x01 | 0xFE | 0xFF),
+            _ => code == 0x00,
+        }
+    }
+}
+
+/// A VID code, held in the low byte of output voltage commands when VOUT_MODE selects VID.
+///
+/// Part II, 8.3
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Vid(pub u16);
+
+impl Vid {
+    /// Decode the voltage of the code in `table`, where codes turning the output off are zero volts.
+    pub fn to_f64(self, table: VidCode) -> Result<f64, FormatError> {
+        let code = u8::try_from(self.0).map_err(|_| FormatError::OutOfRange)?;
+        if table.is_off(code) {
+            return Ok(0.0);
+        }
+        table
+            .millivolts(code)
+            .map(|millivolts| millivolts / 1000.0)
+            .ok_or(FormatError::OutOfRange)
+    }
+
+    /// See [`Vid::to_f64`].
+    pub fn to_f32(self, table: VidCode) -> Result<f32, FormatError> {
+        self.to_f64(table).map(|volts| volts as f32)
+    }
+
+    /// Encode `volts` as the nearest code in `table`, where zero volts turns the output off.
+    ///
+    /// Voltages further than half a step outside of the table are out of range.
+    pub fn from_f64(volts: f64, table: VidCode) -> Result<Self, FormatError> {
+        if !volts.is_finite() {
+            return Err(FormatError::NotFinite);
+        }
+        if volts == 0.0 {
+            return Ok(Self(0x00));
+        }
+        let (offset, step) = table.line();
+        let code = ((volts * 1000.0 - offset) / step).round();
+        let (first, last) = table.codes();
+        if (first as f64..=last as f64).contains(&code) {
+            Ok(Self(code as u16))
+        } else {
+            Err(FormatError::OutOfRange)
+        }
+    }
+
+    /// See [`Vid::from_f64`].
+    pub fn from_f32(volts: f32, table: VidCode) -> Result<Self, FormatError> {
+        Self::from_f64(volts as f64, table)
+    }
+}
+
+impl From<u16> for Vid {
+    fn from(raw: u16) -> Self {
+        Self(raw)
+    }
+}
+
+impl From<Vid> for u16 {
+    fn from(value: Vid) -> Self {
+        value.0
+    }
+}
@@ -0,0 +1,141 @@
+use super::{Half, Linear16};
+use crate::error::FormatError;
+
+/// The data format of output voltage commands, as selected by VOUT_MODE.
+///
+/// Part II, 8.3
+///
+/// Bit 7 selects relative rather than absolute data, the mode is held in bits 6:5 and its parameter in bits 4:0.
+///
+/// Relative data applies to the commands in [`RELATIVE_VOUT_COMMANDS`](crate::commands::RELATIVE_VOUT_COMMANDS),
+/// which then hold a signed value in parts per thousand of VOUT_COMMAND, in the format of the mode.
+/// Other commands are always absolute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VoutMode {
+    /// ULINEAR16, with the five-bit two's complement exponent.
+    ///
+    /// Relative data is a two's complement mantissa instead.
+    Linear { exponent: i8, relative: bool },
+    /// VID, with the five-bit VID code type, see [`VidCode`](super::VidCode).
+    Vid { code: u8 },
+    /// DIRECT, using the coefficients of each command.
+    Direct { relative: bool },
+    /// IEEE 754 half-precision floating point.
+    Half { relative: bool },
+    /// A mode not defined by this implementation, with the raw VOUT_MODE byte.
+    Reserved(u8),
+}
+
+impl VoutMode {
+    /// Whether relative commands hold data relative to VOUT_COMMAND.
+    pub const fn is_relative(self) -> bool {
+        match self {
+            Self::Linear { relative, .. } | Self::Direct { relative } | Self::Half { relative } => {
+                relative
+            }
+            Self::Vid { .. } | Self::Reserved(_) => false,
+        }
+    }
+
+    /// The same mode, with absolute data.
+    pub const fn absolute(self) -> Self {
+        match self {
+            Self::Linear { exponent, .. } => Self::Linear {
+                exponent,
+                relative: false,
+            },
+            Self::Direct { .. } => Self::Direct { relative: false },
+            Self::Half { .. } => Self::Half { relative: false },
+            mode => mode,
+        }
+    }
+
+    /// Decode the raw data of a VOUT-family command, which is signed if the mode is relative.
+    ///
+    /// DIRECT is decoded with the coefficients of each command instead, see [`Direct`](super::Direct),
+    /// and VID with the table of the device, see [`Vid`](super::Vid).
+    pub fn decode(self, raw: u16) -> Result<f64, FormatError> {
+        match self {
+            Self::Linear {
+                exponent,
+                relative: false,
+            } => Ok(Linear16(raw).to_f64(exponent)),
+            Self::Linear {
+                exponent,
+                relative: true,
+            } => Ok(raw as i16 as f64 * 2f64.powi(exponent as i32)),
+            Self::Half { .. } => Ok(Half(raw).to_f64()),
+            _ => Err(FormatError::Unsupported),
+        }
+    }
+
+    /// Encode a value as the raw data of a VOUT-family command, which is signed if the mode is relative.
+    ///
+    /// DIRECT is encoded with the coefficients of each command instead, see [`Direct`](super::Direct),
+    /// and VID with the table of the device, see [`Vid`](super::Vid).
+    pub fn encode(self, value: f64) -> Result<u16, FormatError> {
+        match self {
+            Self::Linear {
+                exponent,
+                relative: false,
+            } => Linear16::from_f64(value, exponent).map(u16::from),
+            Self::Linear {
+                exponent,
+                relative: true,
+            } => {
+                if !value.is_finite() {
+                    return Err(FormatError::NotFinite);
+                }
+                let mantissa = (value * 2f64.powi(-(exponent as i32))).round();
+                if (i16::MIN as f64..=i16::MAX as f64).contains(&mantissa) {
+                    Ok(mantissa as i16 as u16)
+                } else {
+                    Err(FormatError::OutOfRange)
+                }
+            }
+            Self::Half { .. } => Half::from_f64(value).map(u16::from),
+            _ => Err(FormatError::Unsupported),
+        }
+    }
+}
+
+impl Default for VoutMode {
+    fn default() -> Self {
+        Self::Linear {
+            exponent: 0,
+            relative: false,
+        }
+    }
+}
+
+impl From<u8> for VoutMode {
+    fn from(byte: u8) -> Self {
+        let relative = byte & 0x80 != 0;
+        let parameter = byte & 0x1F;
+        match (byte >> 5) & 0b11 {
+            0b00 => Self::Linear {
+                // Sign-extend the five-bit exponent.
+                exponent: ((parameter << 3) as i8) >> 3,
+                relative,
+            },
+            // VID codes are not meaningful relative to anything.
+            0b01 if relative => Self::Reserved(byte),
+            0b01 => Self::Vid { code: parameter },
+            0b10 => Self::Direct { relative },
+            _ => Self::Half { relative },
+        }
+    }
+}
+
+impl From<VoutMode> for u8 {
+    fn from(mode: VoutMode) -> Self {
+        let relative = (mode.is_relative() as u8) << 7;
+        match mode {
+            VoutMode::Linear { exponent, .. } => relative | (exponent as u8 & 0x1F),
+            VoutMode::Vid { code } => (0b01 << 5) | (code & 0x1F),
+            VoutMode::Direct { .. } => relative | (0b10 << 5),
+            VoutMode::Half { .. } => relative | (0b11 << 5),
+            VoutMode::Reserved(byte) => byte,
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! Inventory information, read from the MFR_ and IC_DEVICE_ commands.
+//!
+//! These commands hold ASCII text of a manufacturer defined length, which is often padded with
+//! spaces, `NUL` or `0xFF` (the value of erased memory). [`DeviceIdentity`] holds all of them, and is read and
+//! written with [`PmBusExt::read_identity`](crate::commands::PmBusExt::read_identity) and
+//! [`PmBusExt::provision_identity`](crate::commands::PmBusExt::provision_identity).
+
+/// Decode inventory text, replacing bytes which are not ASCII with `U+FFFD` and trimming padding from both ends.
+pub fn decode_text(bytes: &[u8]) -> String {
+    let is_padding = |byte: &u8| *byte == 0x00 || *byte == 0xFF || byte.is_ascii_whitespace();
+    let start = bytes
+        .iter()
+        .position(|byte| !is_padding(byte))
+        .unwrap_or(bytes.len());
+    let end = bytes
+        .iter()
+        .rposition(|byte| !is_padding(byte))
+        .map_or(start, |end| end + 1);
+    bytes[start..end]
+        .iter()
+        .map(|&byte| {
+            if byte.is_ascii() {
+                byte as char
+            } else {
+                char::REPLACEMENT_CHARACTER
+            }
+        })
+        .collect()
+}
+
+/// A date of manufacture, as held by MFR_DATE in the form `YYMMDD`.
+///
+/// Years are taken to be in the range 2000 to 2099.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MfrDate {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl MfrDate {
+    /// Returns `None` if the date does not exist, or the year cannot be held by MFR_DATE.
+    pub const fn new(year: u16, month: u8, day: u8) -> Option<Self> {
+        if year < 2000 || year > 2099 || month < 1 || month > 12 || day < 1 {
+            return None;
+        }
+        // `is_multiple_of` would raise the minimum supported Rust version to 1.87.
+        #[allow(clippy::manual_is_multiple_of)]
+        let leap = year % 4 == 0;
+        let days = match month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        };
+        if day > days {
+            return None;
+        }
+        Some(Self { year, month, day })
+    }
+
+    /// Parse the `YYMMDD` text of MFR_DATE, ignoring padding.
+    ///
+    /// Returns `None` if the text is not six digits forming a valid date.
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        let text = decode_text(bytes);
+        let digits = text.as_bytes();
+        if digits.len() != 6 || !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        let pair = |index: usize| (digits[index] - b'0') * 10 + (digits[index + 1] - b'0');
+        Self::new(2000 + pair(0) as u16, pair(2), pair(4))
+    }
+
+    pub const fn year(self) -> u16 {
+        self.year
+    }
+
+    pub const fn month(self) -> u8 {
+        self.month
+    }
+
+    pub const fn day(self) -> u8 {
+        self.day
+    }
+
+    /// The `YYMMDD` text to write to MFR_DATE.
+    pub fn to_bytes(self) -> [u8; 6] {
+        let digit = |value: u8| b'0' + value;
+        let year = (self.year % 100) as u8;
+        [
+            digit(year / 10),
+            digit(year % 10),
+            digit(self.month / 10),
+            digit(self.month % 10),
+            digit(self.day / 10),
+            digit(self.day % 10),
+        ]
+    }
+}
+
+/// Displays the date as `YYYY-MM-DD`.
+impl core::fmt::Display for MfrDate {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// The inventory information of a device.
+///
+/// IC_DEVICE_ID and IC_DEVICE_REV identify the controller rather than the product, and are read only.
+/// They were added in PMBus 1.3, and along with MFR_LOCATION and MFR_DATE are `None` when the device does not support them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DeviceIdentity {
+    /// MFR_ID
+    pub manufacturer: String,
+    /// MFR_MODEL
+    pub model: String,
+    /// MFR_REVISION
+    pub revision: String,
+    /// MFR_LOCATION
+    pub location: Option<String>,
+    /// MFR_DATE, or `None` if it is not a valid `YYMMDD` date (such as when it has not been programmed) or is not supported.
+    pub date: Option<MfrDate>,
+    /// MFR_SERIAL
+    pub serial: String,
+    /// IC_DEVICE_ID
+    pub ic_device_id: Option<String>,
+    /// IC_DEVICE_REV
+    pub ic_device_revision: Option<String>,
+}
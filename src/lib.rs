@@ -1,2 +1,16 @@
+pub mod alert;
+pub mod cache;
 pub mod commands;
+pub mod efficiency;
+pub mod energy;
+pub mod error;
+pub mod format;
+pub mod identity;
+pub mod on_off_config;
+pub mod operation;
+pub mod revision;
+#[cfg(feature = "sim")]
+pub mod sim;
 pub mod smbus;
+pub mod status;
+pub mod units;
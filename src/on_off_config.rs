@@ -0,0 +1,175 @@
+//! The ON_OFF_CONFIG command, which configures how the output is turned on and off.
+//!
+//! Part II, 12.2
+//!
+//! ```text
+//! bits 7:5 reserved
+//! bit  4   powerup: wait to be turned on, rather than turning on whenever input power is present
+//! bit  3   respond to the on bit of OPERATION
+//! bit  2   respond to the CONTROL pin
+//! bit  1   CONTROL pin polarity: active high
+//! bit  0   CONTROL pin turn-off action: immediately, rather than following TOFF_DELAY and TOFF_FALL
+//! ```
+
+use crate::error::FormatError;
+
+/// Whether the output turns on as soon as input power is present.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PowerupBehaviour {
+    /// Turn on whenever input power is present, regardless of OPERATION and the CONTROL pin.
+    #[default]
+    Always,
+    /// Turn on only as commanded by OPERATION and/or the CONTROL pin, as enabled.
+    Commanded,
+}
+
+/// The polarity of the CONTROL pin.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ControlPolarity {
+    #[default]
+    ActiveLow,
+    ActiveHigh,
+}
+
+/// How the output turns off when the CONTROL pin is negated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TurnOffAction {
+    /// Follow TOFF_DELAY and TOFF_FALL.
+    #[default]
+    Sequenced,
+    Immediate,
+}
+
+/// The value of ON_OFF_CONFIG.
+///
+/// Converting from a `u8` rejects the reserved bits, while converting to a `u8` (as done when it is written)
+/// also refuses configurations which could never turn the output on, see [`OnOffConfig::validate`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct OnOffConfig {
+    pub powerup: PowerupBehaviour,
+    /// Respond to the on bit of OPERATION.
+    pub command_enabled: bool,
+    /// Require the CONTROL pin to be asserted.
+    pub control_pin_enabled: bool,
+    pub polarity: ControlPolarity,
+    pub turn_off_action: TurnOffAction,
+}
+
+impl OnOffConfig {
+    const POWERUP: u8 = 1 << 4;
+    const COMMAND: u8 = 1 << 3;
+    const CONTROL_PIN: u8 = 1 << 2;
+    const POLARITY: u8 = 1 << 1;
+    const TURN_OFF_ACTION: u8 = 1 << 0;
+    const RESERVED: u8 = 0b111 << 5;
+
+    /// Turn on as soon as input power is present.
+    pub const fn always_on() -> Self {
+        Self {
+            powerup: PowerupBehaviour::Always,
+            command_enabled: false,
+            control_pin_enabled: false,
+            polarity: ControlPolarity::ActiveLow,
+            turn_off_action: TurnOffAction::Sequenced,
+        }
+    }
+
+    /// Turn on and off by OPERATION only.
+    pub const fn by_command() -> Self {
+        Self {
+            powerup: PowerupBehaviour::Commanded,
+            command_enabled: true,
+            ..Self::always_on()
+        }
+    }
+
+    /// Turn on and off by the CONTROL pin only, with the given `polarity`.
+    pub const fn by_control_pin(polarity: ControlPolarity) -> Self {
+        Self {
+            powerup: PowerupBehaviour::Commanded,
+            control_pin_enabled: true,
+            polarity,
+            ..Self::always_on()
+        }
+    }
+
+    /// Turn on when both OPERATION and the CONTROL pin, with the given `polarity`, command it.
+    pub const fn by_command_and_control_pin(polarity: ControlPolarity) -> Self {
+        Self {
+            command_enabled: true,
+            ..Self::by_control_pin(polarity)
+        }
+    }
+
+    pub const fn with_turn_off_action(self, turn_off_action: TurnOffAction) -> Self {
+        Self {
+            turn_off_action,
+            ..self
+        }
+    }
+
+    /// Whether the output can be turned on at all.
+    ///
+    /// Fails with [`FormatError::Invalid`] if the output waits to be commanded on,
+    /// but neither OPERATION nor the CONTROL pin is enabled to do so.
+    pub fn validate(self) -> Result<Self, FormatError> {
+        let commanded = self.powerup == PowerupBehaviour::Commanded;
+        if commanded && !self.command_enabled && !self.control_pin_enabled {
+            return Err(FormatError::Invalid);
+        }
+        Ok(self)
+    }
+}
+
+/// Fails with [`FormatError::Reserved`] if any of the reserved bits are set.
+impl TryFrom<u8> for OnOffConfig {
+    type Error = FormatError;
+
+    fn try_from(raw: u8) -> Result<Self, Self::Error> {
+        if raw & Self::RESERVED != 0 {
+            return Err(FormatError::Reserved);
+        }
+        Ok(Self {
+            powerup: if raw & Self::POWERUP != 0 {
+                PowerupBehaviour::Commanded
+            } else {
+                PowerupBehaviour::Always
+            },
+            command_enabled: raw & Self::COMMAND != 0,
+            control_pin_enabled: raw & Self::CONTROL_PIN != 0,
+            polarity: if raw & Self::POLARITY != 0 {
+                ControlPolarity::ActiveHigh
+            } else {
+                ControlPolarity::ActiveLow
+            },
+            turn_off_action: if raw & Self::TURN_OFF_ACTION != 0 {
+                TurnOffAction::Immediate
+            } else {
+                TurnOffAction::Sequenced
+            },
+        })
+    }
+}
+
+/// Fails if the configuration is not [valid](OnOffConfig::validate).
+impl TryFrom<OnOffConfig> for u8 {
+    type Error = FormatError;
+
+    fn try_from(config: OnOffConfig) -> Result<Self, Self::Error> {
+        let config = config.validate()?;
+        let flag = |set: bool, bit: u8| if set { bit } else { 0 };
+        Ok(flag(
+            config.powerup == PowerupBehaviour::Commanded,
+            OnOffConfig::POWERUP,
+        ) | flag(config.command_enabled, OnOffConfig::COMMAND)
+            | flag(config.control_pin_enabled, OnOffConfig::CONTROL_PIN)
+            | flag(
+                config.polarity == ControlPolarity::ActiveHigh,
+                OnOffConfig::POLARITY,
+            )
+            | flag(
+                config.turn_off_action == TurnOffAction::Immediate,
+                OnOffConfig::TURN_OFF_ACTION,
+            ))
+    }
+}
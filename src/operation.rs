@@ -0,0 +1,225 @@
+//! The OPERATION command, which turns the output on and off and selects margining.
+//!
+//! Part II, 12.1
+//!
+//! ```text
+//! bits 7:6 immediate off, soft off, on or reserved
+//! bits 5:4 voltage source: VOUT_COMMAND, VOUT_MARGIN_LOW, VOUT_MARGIN_HIGH or AVSBus
+//! bits 3:2 margin fault response: none, ignore faults or act on faults
+//! bit  1   transition control
+//! bit  0   reserved
+//! ```
+//!
+//! Bits 5:0 are don't cares while off, and bits 3:2 are don't cares at the nominal voltage.
+
+use crate::error::FormatError;
+
+/// The voltage which the output is regulated to while on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum VoltageSource {
+    #[default]
+    VoutCommand,
+    MarginLow,
+    MarginHigh,
+    /// The voltage is commanded over AVSBus, added in revision 1.3.
+    AvsBus,
+}
+
+/// Whether faults are acted on while margining, such as when margin testing beyond the fault limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MarginFaultResponse {
+    Ignore,
+    Act,
+}
+
+impl MarginFaultResponse {
+    fn from_ignore_faults(ignore_faults: bool) -> Self {
+        if ignore_faults {
+            Self::Ignore
+        } else {
+            Self::Act
+        }
+    }
+}
+
+/// A margin setting, as changed by [`PmBusExt::set_margin`](crate::commands::PmBusExt::set_margin).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Margin {
+    /// Regulate to VOUT_COMMAND.
+    Off,
+    /// Regulate to VOUT_MARGIN_LOW.
+    Low { ignore_faults: bool },
+    /// Regulate to VOUT_MARGIN_HIGH.
+    High { ignore_faults: bool },
+}
+
+/// The value of OPERATION.
+///
+/// Values using reserved combinations of bits are rejected when converting from a `u8`, see [`Operation::try_from`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Operation {
+    pub on: bool,
+    /// Turn off with the TOFF_DELAY and TOFF_FALL sequence, rather than immediately.
+    ///
+    /// Only meaningful while off, and not written while on.
+    pub soft_off: bool,
+    pub source: VoltageSource,
+    /// Required when margining, optional with AVSBus, and ignored at the nominal voltage.
+    pub margin_fault_response: Option<MarginFaultResponse>,
+    /// The transition control bit added in revision 1.3.
+    pub transition_control: bool,
+}
+
+impl Operation {
+    const ON: u8 = 1 << 7;
+    const SOFT_OFF: u8 = 1 << 6;
+    const SOURCE_SHIFT: u8 = 4;
+    const FAULT_RESPONSE_SHIFT: u8 = 2;
+    const TRANSITION_CONTROL: u8 = 1 << 1;
+    const RESERVED: u8 = 1 << 0;
+
+    /// On, regulating to VOUT_COMMAND.
+    pub const fn on() -> Self {
+        Self {
+            on: true,
+            soft_off: false,
+            source: VoltageSource::VoutCommand,
+            margin_fault_response: None,
+            transition_control: false,
+        }
+    }
+
+    /// Off immediately, without sequencing.
+    pub const fn off_immediate() -> Self {
+        Self {
+            on: false,
+            ..Self::on()
+        }
+    }
+
+    /// Off, following TOFF_DELAY and TOFF_FALL.
+    pub const fn soft_off() -> Self {
+        Self {
+            soft_off: true,
+            ..Self::off_immediate()
+        }
+    }
+
+    /// On, regulating to VOUT_MARGIN_HIGH.
+    pub fn margin_high(ignore_faults: bool) -> Self {
+        Self::on().with_margin(Margin::High { ignore_faults })
+    }
+
+    /// On, regulating to VOUT_MARGIN_LOW.
+    pub fn margin_low(ignore_faults: bool) -> Self {
+        Self::on().with_margin(Margin::Low { ignore_faults })
+    }
+
+    /// Replace the voltage source and margin fault response, leaving the other bits unchanged.
+    pub fn with_margin(self, margin: Margin) -> Self {
+        let (source, margin_fault_response) = match margin {
+            Margin::Off => (VoltageSource::VoutCommand, None),
+            Margin::Low { ignore_faults } => (
+                VoltageSource::MarginLow,
+                Some(MarginFaultResponse::from_ignore_faults(ignore_faults)),
+            ),
+            Margin::High { ignore_faults } => (
+                VoltageSource::MarginHigh,
+                Some(MarginFaultResponse::from_ignore_faults(ignore_faults)),
+            ),
+        };
+        Self {
+            source,
+            margin_fault_response,
+            ..self
+        }
+    }
+
+    /// The margin setting, or `None` if the voltage is commanded over AVSBus.
+    pub fn margin(self) -> Option<Margin> {
+        let ignore_faults = self.margin_fault_response == Some(MarginFaultResponse::Ignore);
+        match self.source {
+            VoltageSource::VoutCommand => Some(Margin::Off),
+            VoltageSource::MarginLow => Some(Margin::Low { ignore_faults }),
+            VoltageSource::MarginHigh => Some(Margin::High { ignore_faults }),
+            VoltageSource::AvsBus => None,
+        }
+    }
+
+    /// Decode bits 5:0 as they apply while on.
+    fn decode_on(raw: u8) -> Result<Self, FormatError> {
+        let source = match (raw >> Self::SOURCE_SHIFT) & 0b11 {
+            0b00 => VoltageSource::VoutCommand,
+            0b01 => VoltageSource::MarginLow,
+            0b10 => VoltageSource::MarginHigh,
+            _ => VoltageSource::AvsBus,
+        };
+        // The margin fault response is a don't care at the nominal voltage.
+        let margin_fault_response = match (raw >> Self::FAULT_RESPONSE_SHIFT) & 0b11 {
+            _ if source == VoltageSource::VoutCommand => None,
+            0b00 => None,
+            0b01 => Some(MarginFaultResponse::Ignore),
+            0b10 => Some(MarginFaultResponse::Act),
+            _ => return Err(FormatError::Reserved),
+        };
+        let margining = matches!(source, VoltageSource::MarginLow | VoltageSource::MarginHigh);
+        if raw & Self::RESERVED != 0 || (margining && margin_fault_response.is_none()) {
+            return Err(FormatError::Reserved);
+        }
+        Ok(Self {
+            on: true,
+            soft_off: false,
+            source,
+            margin_fault_response,
+            transition_control: raw & Self::TRANSITION_CONTROL != 0,
+        })
+    }
+}
+
+/// Decodes bits 7:6 first, as the remaining bits only matter while on.
+///
+/// Fails with [`FormatError::Reserved`] if bits 7:6 are `0b11`, or while on if the reserved bit is set,
+/// the margin fault response is `0b11`, or margining is selected without a margin fault response.
+/// While off, bits 5:0 are kept if they form a valid setting for when the output is turned on,
+/// and are otherwise ignored.
+impl TryFrom<u8> for Operation {
+    type Error = FormatError;
+
+    fn try_from(raw: u8) -> Result<Self, Self::Error> {
+        match raw & (Self::ON | Self::SOFT_OFF) {
+            Self::ON => Self::decode_on(raw),
+            0 => Ok(Self {
+                on: false,
+                ..Self::decode_on(raw).unwrap_or_default()
+            }),
+            Self::SOFT_OFF => Ok(Self {
+                on: false,
+                soft_off: true,
+                ..Self::decode_on(raw).unwrap_or_default()
+            }),
+            _ => Err(FormatError::Reserved),
+        }
+    }
+}
+
+impl From<Operation> for u8 {
+    fn from(operation: Operation) -> Self {
+        let source = match operation.source {
+            VoltageSource::VoutCommand => 0b00,
+            VoltageSource::MarginLow => 0b01,
+            VoltageSource::MarginHigh => 0b10,
+            VoltageSource::AvsBus => 0b11,
+        };
+        let margin_fault_response = match operation.margin_fault_response {
+            None => 0b00,
+            Some(MarginFaultResponse::Ignore) => 0b01,
+            Some(MarginFaultResponse::Act) => 0b10,
+        };
+        let flag = |set: bool, bit: u8| if set { bit } else { 0 };
+        flag(operation.on, Operation::ON)
+            | flag(!operation.on && operation.soft_off, Operation::SOFT_OFF)
+            | source << Operation::SOURCE_SHIFT
+            | margin_fault_response << Operation::FAULT_RESPONSE_SHIFT
+            | flag(operation.transition_control, Operation::TRANSITION_CONTROL)
+    }
+}
@@ -0,0 +1,76 @@
+use crate::smbus::SmBusVersion;
+
+/// Revision of one part of the PMBus specification, as encoded in a nibble of `PMBUS_REVISION`.
+///
+/// 22.1, Pg. 88
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Revision {
+    V1_0,
+    V1_1,
+    V1_2,
+    V1_3,
+    /// A revision not known at the time of writing, holding the raw nibble.
+    Unknown(u8),
+}
+
+impl From<u8> for Revision {
+    fn from(nibble: u8) -> Self {
+        match nibble & 0x0F {
+            0b0000 => Self::V1_0,
+            0b0001 => Self::V1_1,
+            0b0010 => Self::V1_2,
+            0b0011 => Self::V1_3,
+            nibble => Self::Unknown(nibble),
+        }
+    }
+}
+
+impl From<Revision> for u8 {
+    fn from(revision: Revision) -> Self {
+        match revision {
+            Revision::V1_0 => 0b0000,
+            Revision::V1_1 => 0b0001,
+            Revision::V1_2 => 0b0010,
+            Revision::V1_3 => 0b0011,
+            Revision::Unknown(nibble) => nibble & 0x0F,
+        }
+    }
+}
+
+/// The value of `PMBUS_REVISION`, the revisions of Part I and Part II that a device complies with.
+///
+/// 22.1, Pg. 88
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PmBusRevision {
+    pub part_i: Revision,
+    pub part_ii: Revision,
+}
+
+impl PmBusRevision {
+    /// The version of SMBus which the device is expected to implement.
+    ///
+    /// Part I revision 1.3 is based on SMBus 3.0, which raised the maximum block size to 255 bytes.
+    /// Unknown revisions are newer than 1.3, and are assumed to keep SMBus 3.0 or later.
+    /// Earlier revisions are limited to SMBus 2.0.
+    pub fn smbus_version(&self) -> SmBusVersion {
+        match self.part_i {
+            Revision::V1_0 | Revision::V1_1 | Revision::V1_2 => SmBusVersion::V2,
+            Revision::V1_3 | Revision::Unknown(_) => SmBusVersion::V3,
+        }
+    }
+}
+
+impl From<u8> for PmBusRevision {
+    fn from(byte: u8) -> Self {
+        Self {
+            part_i: Revision::from(byte >> 4),
+            part_ii: Revision::from(byte),
+        }
+    }
+}
+
+impl From<PmBusRevision> for u8 {
+    fn from(revision: PmBusRevision) -> Self {
+        u8::from(revision.part_i) << 4 | u8::from(revision.part_ii)
+    }
+}
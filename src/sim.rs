@@ -0,0 +1,129 @@
+//! In-memory PMBus devices, for testing code built on [`PmBus`] without hardware.
+//!
+//! ```
+//! use pmbus::commands::{PmBus, VOUT_COMMAND, VOUT_MODE};
+//! use pmbus::sim::{SimulatedBus, SimulatedDevice};
+//! use pmbus::units::Volts;
+//!
+//! // ULINEAR16 with an exponent of -9.
+//! let device = SimulatedDevice::new(2).with_register(VOUT_MODE, &[0x17]);
+//! let mut bus = SimulatedBus::new().with_device(0x40, device);
+//! pollster::block_on(bus.send_vout_command(0x40, Volts(1.2))).unwrap();
+//! assert_eq!(bus.device(0x40).unwrap().register(0, VOUT_COMMAND), Some(&[0x66, 0x02][..]));
+//! ```
+
+use std::collections::BTreeMap;
+
+use embedded_hal::i2c::{ErrorKind, ErrorType, NoAcknowledgeSource, Operation, SevenBitAddress};
+
+use crate::commands::PmBus;
+
+mod device;
+
+pub use self::device::{SimulatedDevice, DEFAULT_CAPABILITY, DEFAULT_PMBUS_REVISION};
+
+/// An I2C bus hosting [`SimulatedDevice`]s, implementing both the async and blocking `I2c` traits.
+///
+/// Transactions addressed to an empty address are not acknowledged.
+/// Within a transaction, the bytes written before the first read are the request,
+/// and the device response is spread across the reads which follow.
+/// Reading past the end of the response returns `0xFF`, as an idle bus would.
+#[derive(Debug, Clone, Default)]
+pub struct SimulatedBus {
+    devices: BTreeMap<SevenBitAddress, SimulatedDevice>,
+    pec: bool,
+}
+
+impl SimulatedBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Host `device` at `address`, replacing any device already there.
+    pub fn with_device(mut self, address: SevenBitAddress, device: SimulatedDevice) -> Self {
+        self.devices.insert(address, device);
+        self
+    }
+
+    /// Use PEC for every transaction made through [`SmBus`](crate::smbus::SmBus).
+    pub fn with_pec(self) -> Self {
+        Self { pec: true, ..self }
+    }
+
+    pub fn device(&self, address: SevenBitAddress) -> Option<&SimulatedDevice> {
+        self.devices.get(&address)
+    }
+
+    pub fn device_mut(&mut self, address: SevenBitAddress) -> Option<&mut SimulatedDevice> {
+        self.devices.get_mut(&address)
+    }
+}
+
+impl ErrorType for SimulatedBus {
+    type Error = ErrorKind;
+}
+
+impl embedded_hal::i2c::I2c for SimulatedBus {
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let device = self
+            .devices
+            .get_mut(&address)
+            .ok_or(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))?;
+        let mut request = Vec::new();
+        let mut response: Option<std::vec::IntoIter<u8>> = None;
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => {
+                    // A write following a read starts a new request.
+                    if response.take().is_some() {
+                        request.clear();
+                    }
+                    request.extend_from_slice(bytes);
+                }
+                Operation::Read(buf) => {
+                    let response = match &mut response {
+                        Some(response) => response,
+                        None => response.insert(device.read(address, &request)?.into_iter()),
+                    };
+                    for byte in buf.iter_mut() {
+                        *byte = response.next().unwrap_or(0xFF);
+                    }
+                }
+            }
+        }
+        if response.is_none() {
+            device.write(address, &request)?;
+        }
+        Ok(())
+    }
+}
+
+impl embedded_hal_async::i2c::I2c for SimulatedBus {
+    async fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        embedded_hal::i2c::I2c::transaction(self, address, operations)
+    }
+}
+
+impl crate::smbus::SmBus for SimulatedBus {
+    fn pec_enabled(&self, _address: SevenBitAddress) -> bool {
+        self.pec
+    }
+}
+
+impl crate::smbus::blocking::SmBus for SimulatedBus {
+    fn pec_enabled(&self, _address: SevenBitAddress) -> bool {
+        self.pec
+    }
+}
+
+impl PmBus for SimulatedBus {}
+
+impl crate::commands::blocking::PmBus for SimulatedBus {}
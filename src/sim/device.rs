@@ -0,0 +1,434 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource, SevenBitAddress};
+
+use crate::commands::*;
+use crate::format::Coefficients;
+use crate::smbus::Pec;
+use crate::status::{StatusCml, StatusWord};
+
+const CML_INVALID_COMMAND: u8 = StatusCml::INVALID_COMMAND.bits();
+const CML_INVALID_DATA: u8 = StatusCml::INVALID_DATA.bits();
+const CML_PEC_FAILED: u8 = StatusCml::PEC_FAILED.bits();
+
+/// CAPABILITY answered by default: PEC, 400 kHz and `SMBALERT#`.
+pub const DEFAULT_CAPABILITY: u8 = 0b1011_0000;
+
+/// PMBUS_REVISION answered by default: Part I and Part II revision 1.3.
+pub const DEFAULT_PMBUS_REVISION: u8 = 0x33;
+
+/// QUERY format bits for a command which does not return numeric data.
+const QUERY_NOT_NUMERIC: u8 = 0b111 << 2;
+
+/// Commands which apply to the whole device, rather than to the selected page.
+const GLOBAL_COMMANDS: &[u8] = &[
+    WRITE_PROTECT,
+    CAPABILITY,
+    PMBUS_REVISION,
+    MFR_ID,
+    MFR_MODEL,
+    MFR_REVISION,
+    MFR_LOCATION,
+    MFR_DATE,
+    MFR_SERIAL,
+    APP_PROFILE_SUPPORT,
+    IC_DEVICE_ID,
+    IC_DEVICE_REV,
+];
+
+/// Status registers cleared by CLEAR_FAULTS.
+const STATUS_COMMANDS: &[u8] = &[
+    STATUS_WORD,
+    STATUS_VOUT,
+    STATUS_IOUT,
+    STATUS_INPUT,
+    STATUS_TEMPERATURE,
+    STATUS_CML,
+    STATUS_OTHER,
+    STATUS_MFR_SPECIFIC,
+    STATUS_FANS_1_2,
+    STATUS_FANS_3_4,
+];
+
+/// Commands answered by the device itself, which have no register.
+const COMPUTED_COMMANDS: &[u8] = &[PAGE, QUERY, SMBALERT_MASK, COEFFICIENTS, STATUS_BYTE];
+
+/// A virtual PMBus device, hosted by a [`SimulatedBus`](super::SimulatedBus).
+///
+/// Every command in [`COMMANDS`] which can be written or read is supported,
+/// with a register per page that starts out zeroed (or empty, for blocks).
+/// Reads and writes are checked against the table, so that writing a read-only command,
+/// or reading a write-only one, is refused as an invalid command.
+///
+/// Refused transactions set the corresponding bit of STATUS_CML (along with the CML bit of STATUS_WORD)
+/// and are not acknowledged, see [`StatusCml::INVALID_COMMAND`], [`StatusCml::INVALID_DATA`] and [`StatusCml::PEC_FAILED`].
+///
+/// PEC is supported when bit 7 of CAPABILITY is set, which it is by default.
+/// Writes are then accepted with or without a trailing PEC byte, and a PEC byte follows every response.
+#[derive(Debug, Clone)]
+pub struct SimulatedDevice {
+    supported: BTreeSet<u8>,
+    global: BTreeMap<u8, Vec<u8>>,
+    pages: Vec<BTreeMap<u8, Vec<u8>>>,
+    page: u8,
+    queries: BTreeMap<u8, u8>,
+    smbalert_masks: BTreeMap<u8, u8>,
+    coefficients: BTreeMap<(u8, bool), [u8; 5]>,
+}
+
+impl SimulatedDevice {
+    /// A device with `pages` pages, at least one.
+    pub fn new(pages: u8) -> Self {
+        let mut supported = BTreeSet::new();
+        let mut global = BTreeMap::new();
+        let mut registers = BTreeMap::new();
+        for info in COMMANDS {
+            if info.write.is_none() && info.read.is_none() {
+                continue;
+            }
+            // PAGE_PLUS_WRITE and PAGE_PLUS_READ are not simulated.
+            if info.code == PAGE_PLUS_WRITE || info.code == PAGE_PLUS_READ {
+                continue;
+            }
+            supported.insert(info.code);
+            if COMPUTED_COMMANDS.contains(&info.code) || info.write == Some(WriteKind::Send) {
+                continue;
+            }
+            let value = vec![0x00; info.size.filter(|_| !info.is_block()).unwrap_or(0)];
+            if GLOBAL_COMMANDS.contains(&info.code) {
+                global.insert(info.code, value);
+            } else {
+                registers.insert(info.code, value);
+            }
+        }
+        global.insert(CAPABILITY, vec![DEFAULT_CAPABILITY]);
+        global.insert(PMBUS_REVISION, vec![DEFAULT_PMBUS_REVISION]);
+        Self {
+            supported,
+            global,
+            pages: vec![registers; pages.max(1) as usize],
+            page: 0,
+            queries: BTreeMap::new(),
+            smbalert_masks: BTreeMap::new(),
+            coefficients: BTreeMap::new(),
+        }
+    }
+
+    /// Set the value of CAPABILITY.
+    pub fn with_capability(mut self, capability: u8) -> Self {
+        self.global.insert(CAPABILITY, vec![capability]);
+        self
+    }
+
+    /// Answer QUERY for `command` with `query`, rather than with the answer derived from the table.
+    pub fn with_query(mut self, command: u8, query: u8) -> Self {
+        self.queries.insert(command, query);
+        self
+    }
+
+    /// Answer COEFFICIENTS for `command`, when asked for the coefficients used to read (`read == true`) or write it.
+    pub fn with_coefficients(
+        mut self,
+        command: u8,
+        read: bool,
+        coefficients: Coefficients,
+    ) -> Self {
+        self.coefficients
+            .insert((command, read), coefficients.into());
+        self
+    }
+
+    /// Set the register of `command` to `value` on every page.
+    pub fn with_register(mut self, command: u8, value: &[u8]) -> Self {
+        for page in 0..self.pages.len() as u8 {
+            self.set_register(page, command, value);
+        }
+        self
+    }
+
+    /// Remove support for `command`, so that it is refused as an invalid command.
+    pub fn without_command(mut self, command: u8) -> Self {
+        self.supported.remove(&command);
+        self.global.remove(&command);
+        for registers in &mut self.pages {
+            registers.remove(&command);
+        }
+        self
+    }
+
+    /// The currently selected page, as last written to PAGE.
+    pub fn page(&self) -> u8 {
+        self.page
+    }
+
+    pub fn pages(&self) -> u8 {
+        self.pages.len() as u8
+    }
+
+    /// The value of the register of `command` on `page`.
+    ///
+    /// Returns `None` if the command has no register, or the page does not exist.
+    pub fn register(&self, page: u8, command: u8) -> Option<&[u8]> {
+        if GLOBAL_COMMANDS.contains(&command) {
+            return self.global.get(&command).map(Vec::as_slice);
+        }
+        self.pages
+            .get(page as usize)?
+            .get(&command)
+            .map(Vec::as_slice)
+    }
+
+    /// Set the register of `command` on `page` to `value`, as the device itself would.
+    ///
+    /// This is how measurements and faults are injected, and is not subject to read-only enforcement.
+    /// Does nothing if the command has no register, or the page does not exist.
+    pub fn set_register(&mut self, page: u8, command: u8, value: &[u8]) {
+        let register = if GLOBAL_COMMANDS.contains(&command) {
+            self.global.get_mut(&command)
+        } else {
+            self.pages
+                .get_mut(page as usize)
+                .and_then(|registers| registers.get_mut(&command))
+        };
+        if let Some(register) = register {
+            *register = value.to_vec();
+        }
+    }
+
+    /// Handle a write transaction of `bytes`, as `[command, data.., pec?]`.
+    pub(super) fn write(
+        &mut self,
+        address: SevenBitAddress,
+        bytes: &[u8],
+    ) -> Result<(), ErrorKind> {
+        // Quick Command.
+        if bytes.is_empty() {
+            return Ok(());
+        }
+        self.write_command(address, bytes)
+            .map_err(|cml| self.refuse(cml))
+    }
+
+    /// Handle a read transaction, after `request` was written as `[command, data..]`.
+    ///
+    /// Returns the response, followed by a PEC byte when supported.
+    pub(super) fn read(
+        &mut self,
+        address: SevenBitAddress,
+        request: &[u8],
+    ) -> Result<Vec<u8>, ErrorKind> {
+        let mut response = self.read_command(request).map_err(|cml| self.refuse(cml))?;
+        if self.pec_supported() {
+            let pec = Pec::new()
+                .address(address, false)
+                .update(request)
+                .address(address, true)
+                .update(&response)
+                .value();
+            response.push(pec);
+        }
+        Ok(response)
+    }
+
+    fn write_command(&mut self, address: SevenBitAddress, bytes: &[u8]) -> Result<(), u8> {
+        let (&command, rest) = bytes.split_first().ok_or(CML_INVALID_COMMAND)?;
+        let info = self.info(command)?;
+        let write = info.write.ok_or(CML_INVALID_COMMAND)?;
+        let len = match write {
+            WriteKind::Send => 0,
+            WriteKind::Write if info.is_block() => {
+                1 + *rest.first().ok_or(CML_INVALID_DATA)? as usize
+            }
+            WriteKind::Write => info.size.unwrap_or(0),
+        };
+        if rest.len() == len + 1 && self.pec_supported() {
+            let (data, received) = bytes.split_at(bytes.len() - 1);
+            if Pec::new().address(address, false).update(data).value() != received[0] {
+                return Err(CML_PEC_FAILED);
+            }
+        } else if rest.len() != len {
+            return Err(CML_INVALID_DATA);
+        }
+        let data = match write {
+            WriteKind::Write if info.is_block() => &rest[1..len],
+            _ => &rest[..len],
+        };
+
+        match command {
+            PAGE => {
+                let page = data[0];
+                if page != 0xFF && page as usize >= self.pages.len() {
+                    return Err(CML_INVALID_DATA);
+                }
+                self.page = page;
+            }
+            CLEAR_FAULTS => {
+                for page in self.selected_pages() {
+                    for &status in STATUS_COMMANDS {
+                        self.update_register(page, status, |value| value.fill(0x00));
+                    }
+                }
+            }
+            SMBALERT_MASK => {
+                self.smbalert_masks.insert(data[0], data[1]);
+            }
+            STATUS_BYTE => self.clear_status(STATUS_WORD, &[data[0], 0x00]),
+            command if STATUS_COMMANDS.contains(&command) => self.clear_status(command, data),
+            _ if write == WriteKind::Send => {}
+            command if GLOBAL_COMMANDS.contains(&command) => {
+                self.global.insert(command, data.to_vec());
+            }
+            command => {
+                for page in self.selected_pages() {
+                    self.update_register(page, command, |value| *value = data.to_vec());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn read_command(&mut self, request: &[u8]) -> Result<Vec<u8>, u8> {
+        let (&command, rest) = request.split_first().ok_or(CML_INVALID_COMMAND)?;
+        let info = self.info(command)?;
+        match info.read.ok_or(CML_INVALID_COMMAND)? {
+            ReadKind::Read => {
+                if !rest.is_empty() {
+                    return Err(CML_INVALID_DATA);
+                }
+                let data = match command {
+                    PAGE => vec![self.page],
+                    // STATUS_WORD may have been given any value by the user, including none.
+                    STATUS_BYTE => {
+                        vec![self
+                            .paged_register(STATUS_WORD)?
+                            .first()
+                            .copied()
+                            .unwrap_or(0x00)]
+                    }
+                    command if GLOBAL_COMMANDS.contains(&command) => {
+                        self.global.get(&command).cloned().unwrap_or_default()
+                    }
+                    command => self.paged_register(command)?.to_vec(),
+                };
+                if info.is_block() {
+                    Ok(block(&data))
+                } else {
+                    Ok(data)
+                }
+            }
+            ReadKind::Call => {
+                let (&count, data) = rest.split_first().ok_or(CML_INVALID_DATA)?;
+                if count as usize != data.len() {
+                    return Err(CML_INVALID_DATA);
+                }
+                let response = match (command, data) {
+                    (QUERY, &[code]) => vec![self.query(code)],
+                    (SMBALERT_MASK, &[code]) => {
+                        vec![self.smbalert_masks.get(&code).copied().unwrap_or(0x00)]
+                    }
+                    (COEFFICIENTS, &[code, read]) => self
+                        .coefficients
+                        .get(&(code, read != 0))
+                        .ok_or(CML_INVALID_DATA)?
+                        .to_vec(),
+                    _ => return Err(CML_INVALID_DATA),
+                };
+                Ok(block(&response))
+            }
+        }
+    }
+
+    /// Look up a supported command.
+    fn info(&self, command: u8) -> Result<&'static CommandInfo, u8> {
+        CommandInfo::get(command)
+            .filter(|info| self.supported.contains(&info.code))
+            .ok_or(CML_INVALID_COMMAND)
+    }
+
+    /// The answer to QUERY for `command`.
+    fn query(&self, command: u8) -> u8 {
+        if let Some(&query) = self.queries.get(&command) {
+            return query;
+        }
+        match CommandInfo::get(command).filter(|info| self.supported.contains(&info.code)) {
+            Some(info) => {
+                (1 << 7)
+                    | ((info.write.is_some() as u8) << 6)
+                    | ((info.read.is_some() as u8) << 5)
+                    | QUERY_NOT_NUMERIC
+            }
+            None => 0x00,
+        }
+    }
+
+    fn pec_supported(&self) -> bool {
+        self.global
+            .get(&CAPABILITY)
+            .is_some_and(|capability| capability.first().is_some_and(|byte| byte & 0x80 != 0))
+    }
+
+    /// Pages affected by a write, which is every page when PAGE is `0xFF`.
+    fn selected_pages(&self) -> std::ops::Range<u8> {
+        if self.page == 0xFF {
+            0..self.pages.len() as u8
+        } else {
+            self.page..self.page + 1
+        }
+    }
+
+    /// The register of `command` on the selected page, which must not be every page.
+    fn paged_register(&self, command: u8) -> Result<&[u8], u8> {
+        self.pages
+            .get(self.page as usize)
+            .ok_or(CML_INVALID_DATA)?
+            .get(&command)
+            .map(Vec::as_slice)
+            .ok_or(CML_INVALID_COMMAND)
+    }
+
+    fn update_register(&mut self, page: u8, command: u8, f: impl FnOnce(&mut Vec<u8>)) {
+        if let Some(register) = self.pages[page as usize].get_mut(&command) {
+            f(register);
+        }
+    }
+
+    /// Writing a status register clears the bits which are set in `data`.
+    fn clear_status(&mut self, command: u8, data: &[u8]) {
+        for page in self.selected_pages() {
+            self.update_register(page, command, |value| {
+                for (byte, clear) in value.iter_mut().zip(data) {
+                    *byte &= !clear;
+                }
+            });
+        }
+    }
+
+    /// Record a refused transaction in STATUS_CML and STATUS_WORD, and refuse to acknowledge it.
+    fn refuse(&mut self, cml: u8) -> ErrorKind {
+        for page in self.selected_pages() {
+            // Registers may have been given any value by the user, so they are extended to their size first.
+            self.update_register(page, STATUS_CML, |value| {
+                if value.is_empty() {
+                    value.push(0x00);
+                }
+                value[0] |= cml;
+            });
+            self.update_register(page, STATUS_WORD, |value| {
+                if value.len() < 2 {
+                    value.resize(2, 0x00);
+                }
+                let word = u16::from_le_bytes([value[0], value[1]]) | StatusWord::CML.bits();
+                value[..2].copy_from_slice(&word.to_le_bytes());
+            });
+        }
+        ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data)
+    }
+}
+
+/// Prefix `data` with its byte count.
+fn block(data: &[u8]) -> Vec<u8> {
+    let mut block = Vec::with_capacity(1 + data.len());
+    block.push(data.len() as u8);
+    block.extend_from_slice(data);
+    block
+}
@@ -10,138 +10,521 @@
 // <https://github.com/CBJamo/smbus-adapter/blob/main/src/lib.rs>
 // That code also has no license, which is a problem, although it is too trivial to hold copyright.
 
-use embedded_hal_async::i2c::{AddressMode, I2c, Operation, SevenBitAddress};
+use embedded_hal::i2c::{AddressMode, Error, ErrorKind};
+use embedded_hal_async::i2c::{I2c, Operation, SevenBitAddress};
 
-// TODO: Dig deeper, the specification claims that the maximum block size is 255 bits,
-// but as we all know, 32 * 8 = 256. Why do other crates assume 32 bytes here and what is the last/first bit supposed to be used for?
-pub const SMBUS_MAX_BLOCK_SIZE: usize = 32;
+use crate::error::SmBusError;
 
-/// Based on System Management Bus (SMBus) Specification Version 3.2.
-///
-/// <https://smbus.org/specs/SMBus_3_2_20220112.pdf>
-///
-/// The implementation is not comprehensive, and omits several legacy transactions including read/write N-bytes protocols.
-///
-/// Also missing is SMBus Host Notify protocol (6.5.9, Pg. 44) which will later be necessary
-/// to interrupt the host when a device on the bus has set the `SMBALERT#` bit.
-/// This is not critical, as there are often alternative ways to listen for that signal.
+pub mod arp;
+pub mod blocking;
+pub mod pec;
+
+pub use self::pec::Pec;
+
+/// The Alert Response Address, read by the host to find which device asserted `SMBALERT#`.
+pub const ALERT_RESPONSE_ADDRESS: SevenBitAddress = 0x0C;
+
+/// Address modes which can take part in an SMBus transaction.
 ///
-/// This trait currently does not handle Packet Error Correction.
-/// PEC can be implemented later with either a `const` boolean generic parameter,
-/// or with a Cargo feature.
-#[async_trait::async_trait(?Send)]
-pub trait SmBus<A: AddressMode = SevenBitAddress>: I2c<A> {
-    /// 6.5.1, Pg. 38
-    async fn quick_command(&mut self, address: A, bit: bool) -> Result<(), Self::Error> {
-        if bit {
-            self.read(address, &mut []).await
-        } else {
-            self.write(address, &[]).await
-        }
-    }
+/// SMBus only defines 7-bit addressing (10-bit addresses are reserved),
+/// so this is only implemented for [`SevenBitAddress`].
+pub trait SmBusAddress: AddressMode + Copy + Ord {
+    /// See [`ALERT_RESPONSE_ADDRESS`].
+    const ALERT_RESPONSE_ADDRESS: Self;
 
-    /// 6.5.2, Pg. 38-39
-    async fn send_byte(&mut self, address: A, byte: u8) -> Result<(), Self::Error> {
-        self.write(address, &[byte]).await
-    }
+    /// The address byte as transmitted on the wire, including the R/W bit.
+    fn pec_byte(self, read: bool) -> u8;
+
+    /// Decode the address of a device from the byte it returned for a read of the Alert Response Address.
+    fn from_alert_response(byte: u8) -> Self;
+}
 
-    /// 6.5.3, Pg. 39
-    async fn receive_byte(&mut self, address: A) -> Result<u8, Self::Error> {
-        let mut buf = [0x00];
-        self.read(address, &mut buf).await?;
-        Ok(buf[0])
+impl SmBusAddress for SevenBitAddress {
+    const ALERT_RESPONSE_ADDRESS: Self = ALERT_RESPONSE_ADDRESS;
+
+    fn pec_byte(self, read: bool) -> u8 {
+        (self << 1) | read as u8
     }
 
-    /// 6.5.4, Pg. 39-40
-    async fn write_byte(&mut self, address: A, command: u8, byte: u8) -> Result<(), Self::Error> {
-        self.write(address, &[command, byte]).await
+    fn from_alert_response(byte: u8) -> Self {
+        // The device address is sent in the upper seven bits, the lowest bit is unused.
+        byte >> 1
     }
+}
+
+/// Maximum block size of SMBus 2.0 and earlier, and the default for every device.
+pub const SMBUS_MAX_BLOCK_SIZE: usize = 32;
 
-    /// 6.5.4, Pg. 39-40
-    async fn write_word(&mut self, address: A, command: u8, word: u16) -> Result<(), Self::Error> {
-        let word = word.to_le_bytes();
-        self.write(address, &[command, word[0], word[1]]).await
+/// Maximum block size of SMBus 3.0 and later.
+///
+/// The byte count of a block is a single byte, so a block may be up to 255 bytes long (6.5.7, Pg. 42).
+/// The previous limit of 32 bytes is why other implementations assume blocks to be 32 bytes.
+pub const SMBUS_3_MAX_BLOCK_SIZE: usize = 255;
+
+/// Version of the SMBus specification implemented by a device, as far as it affects the protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SmBusVersion {
+    /// SMBus 2.0, and earlier.
+    V2,
+    /// SMBus 3.0, and later.
+    V3,
+}
+
+impl SmBusVersion {
+    pub const fn max_block_size(self) -> usize {
+        match self {
+            Self::V2 => SMBUS_MAX_BLOCK_SIZE,
+            Self::V3 => SMBUS_3_MAX_BLOCK_SIZE,
+        }
     }
+}
+
+/// Define the `SmBus` trait over the `I2c` and `Operation` in scope where it is invoked,
+/// with `async` methods awaiting the bus, or with blocking methods.
+///
+/// Both [`SmBus`] and [`blocking::SmBus`] are defined by this, so that they cannot drift apart.
+macro_rules! smbus_trait {
+    ($(#[$attr:meta])* [$($async:ident)?] [$($await:tt)*]) => {
+        $(#[$attr])*
+        pub trait SmBus<A: SmBusAddress = SevenBitAddress>: I2c<A> {
+            /// Whether Packet Error Checking is used for transactions with the device at `address`.
+            ///
+            /// Defaults to `false`. Override this to enable PEC for the whole bus, or per device
+            /// (for example, after checking bit 7 of the device's `CAPABILITY` register).
+            fn pec_enabled(&self, address: A) -> bool {
+                let _ = address;
+                false
+            }
+
+            /// The largest block, in bytes, which may be transferred to or from the device at `address`.
+            ///
+            /// Defaults to [`SMBUS_MAX_BLOCK_SIZE`](crate::smbus::SMBUS_MAX_BLOCK_SIZE), the SMBus 2.0 limit.
+            /// Override this to return [`SMBUS_3_MAX_BLOCK_SIZE`](crate::smbus::SMBUS_3_MAX_BLOCK_SIZE) for the whole bus, or per device
+            /// (for example, based on [`SmBusVersion`](crate::smbus::SmBusVersion) as negotiated from the `PMBUS_REVISION` command).
+            fn max_block_size(&self, address: A) -> usize {
+                let _ = address;
+                SMBUS_MAX_BLOCK_SIZE
+            }
+
+            /// 6.5.1, Pg. 38
+            ///
+            /// Quick Command does not support PEC.
+            $($async)? fn quick_command(
+                &mut self,
+                address: A,
+                bit: bool,
+            ) -> Result<(), SmBusError<Self::Error>> {
+                if bit {
+                    self.read(address, &mut [])$($await)* ?;
+                } else {
+                    self.write(address, &[])$($await)* ?;
+                }
+                Ok(())
+            }
+
+            /// 6.5.2, Pg. 38-39
+            $($async)? fn send_byte(&mut self, address: A, byte: u8) -> Result<(), SmBusError<Self::Error>> {
+                let pec = self.pec_enabled(address);
+                let mut buf = [byte, 0x00];
+                self.write(address, seal(address, pec, &mut buf))$($await)* ?;
+                Ok(())
+            }
+
+            /// 6.5.3, Pg. 39
+            $($async)? fn receive_byte(&mut self, address: A) -> Result<u8, SmBusError<Self::Error>> {
+                let pec = self.pec_enabled(address);
+                let mut buf = [0x00, 0x00];
+                self.read(address, &mut buf[..1 + pec as usize])$($await)* ?;
+                if pec {
+                    verify(Pec::new().address(address, true), &buf[..1], buf[1])?;
+                }
+                Ok(buf[0])
+            }
+
+            /// 6.5.4, Pg. 39-40
+            $($async)? fn write_byte(
+                &mut self,
+                address: A,
+                command: u8,
+                byte: u8,
+            ) -> Result<(), SmBusError<Self::Error>> {
+                let pec = self.pec_enabled(address);
+                let mut buf = [command, byte, 0x00];
+                self.write(address, seal(address, pec, &mut buf))$($await)* ?;
+                Ok(())
+            }
+
+            /// 6.5.4, Pg. 39-40
+            $($async)? fn write_word(
+                &mut self,
+                address: A,
+                command: u8,
+                word: u16,
+            ) -> Result<(), SmBusError<Self::Error>> {
+                let pec = self.pec_enabled(address);
+                let word = word.to_le_bytes();
+                let mut buf = [command, word[0], word[1], 0x00];
+                self.write(address, seal(address, pec, &mut buf))$($await)* ?;
+                Ok(())
+            }
+
+            /// 6.5.4, Pg. 39-40
+            ///
+            /// Write 32 was added in SMBus 3.0.
+            $($async)? fn write_32(
+                &mut self,
+                address: A,
+                command: u8,
+                data: u32,
+            ) -> Result<(), SmBusError<Self::Error>> {
+                let pec = self.pec_enabled(address);
+                let mut buf = [0x00; 6];
+                buf[0] = command;
+                buf[1..5].copy_from_slice(&data.to_le_bytes());
+                self.write(address, seal(address, pec, &mut buf))$($await)* ?;
+                Ok(())
+            }
 
-    /// 6.5.5, Pg. 40-41
-    async fn read_byte(&mut self, address: A, command: u8) -> Result<u8, Self::Error> {
-        let mut buf = [0x00];
-        self.write_read(address, &[command], &mut buf).await?;
-        Ok(buf[0])
+            /// 6.5.4, Pg. 39-40
+            ///
+            /// Write 64 was added in SMBus 3.0.
+            $($async)? fn write_64(
+                &mut self,
+                address: A,
+                command: u8,
+                data: u64,
+            ) -> Result<(), SmBusError<Self::Error>> {
+                let pec = self.pec_enabled(address);
+                let mut buf = [0x00; 10];
+                buf[0] = command;
+                buf[1..9].copy_from_slice(&data.to_le_bytes());
+                self.write(address, seal(address, pec, &mut buf))$($await)* ?;
+                Ok(())
+            }
+
+            /// 6.5.5, Pg. 40-41
+            $($async)? fn read_byte(&mut self, address: A, command: u8) -> Result<u8, SmBusError<Self::Error>> {
+                let pec = self.pec_enabled(address);
+                let mut buf = [0x00, 0x00];
+                self.write_read(address, &[command], &mut buf[..1 + pec as usize])$($await)* ?;
+                if pec {
+                    verify(read_pec(address, &[command]), &buf[..1], buf[1])?;
+                }
+                Ok(buf[0])
+            }
+
+            /// 6.5.5, Pg. 40-41
+            $($async)? fn read_word(&mut self, address: A, command: u8) -> Result<u16, SmBusError<Self::Error>> {
+                let pec = self.pec_enabled(address);
+                let mut buf = [0x00, 0x00, 0x00];
+                self.write_read(address, &[command], &mut buf[..2 + pec as usize])$($await)* ?;
+                if pec {
+                    verify(read_pec(address, &[command]), &buf[..2], buf[2])?;
+                }
+                Ok(u16::from_le_bytes([buf[0], buf[1]]))
+            }
+
+            /// 6.5.5, Pg. 40-41
+            ///
+            /// Read 32 was added in SMBus 3.0.
+            $($async)? fn read_32(&mut self, address: A, command: u8) -> Result<u32, SmBusError<Self::Error>> {
+                let pec = self.pec_enabled(address);
+                let mut buf = [0x00; 5];
+                self.write_read(address, &[command], &mut buf[..4 + pec as usize])$($await)* ?;
+                if pec {
+                    verify(read_pec(address, &[command]), &buf[..4], buf[4])?;
+                }
+                Ok(u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]))
+            }
+
+            /// 6.5.5, Pg. 40-41
+            ///
+            /// Read 64 was added in SMBus 3.0.
+            $($async)? fn read_64(&mut self, address: A, command: u8) -> Result<u64, SmBusError<Self::Error>> {
+                let pec = self.pec_enabled(address);
+                let mut buf = [0x00; 9];
+                self.write_read(address, &[command], &mut buf[..8 + pec as usize])$($await)* ?;
+                if pec {
+                    verify(read_pec(address, &[command]), &buf[..8], buf[8])?;
+                }
+                let mut data = [0x00; 8];
+                data.copy_from_slice(&buf[..8]);
+                Ok(u64::from_le_bytes(data))
+            }
+
+            /// 6.5.6, Pg. 41
+            $($async)? fn process_call(
+                &mut self,
+                address: A,
+                command: u8,
+                word: u16,
+            ) -> Result<u16, SmBusError<Self::Error>> {
+                let pec = self.pec_enabled(address);
+                let word = word.to_le_bytes();
+                let write = [command, word[0], word[1]];
+                let mut buf = [0x00, 0x00, 0x00];
+                self.write_read(address, &write, &mut buf[..2 + pec as usize])$($await)* ?;
+                if pec {
+                    verify(read_pec(address, &write), &buf[..2], buf[2])?;
+                }
+                Ok(u16::from_le_bytes([buf[0], buf[1]]))
+            }
+
+            /// 6.5.7, Pg. 42
+            $($async)? fn block_write(
+                &mut self,
+                address: A,
+                command: u8,
+                block: &[u8],
+            ) -> Result<(), SmBusError<Self::Error>> {
+                let (pec, max) = (self.pec_enabled(address), self.max_block_size(address));
+                let buf = pack_block(address, pec, max, command, block)?;
+                self.write(address, &buf)$($await)* ?;
+                Ok(())
+            }
+
+            /// 6.5.7, Pg. 42
+            ///
+            /// The command code is written, followed by a repeated start and the read of the byte count and data,
+            /// all in a single transaction.
+            ///
+            /// An I2C transaction must be fully described before it begins, so the host cannot size the data read
+            /// from the byte count as it arrives. Instead, enough bytes are read for the largest permissible block,
+            /// and anything after the reported byte count (and PEC) is discarded.
+            /// If the size of the block is known in advance, prefer [`SmBus::block_read_exact`].
+            $($async)? fn block_read(
+                &mut self,
+                address: A,
+                command: u8,
+            ) -> Result<Vec<u8>, SmBusError<Self::Error>> {
+                let (pec, max) = (self.pec_enabled(address), self.max_block_size(address));
+                // The first byte is the byte count, and when PEC is enabled there is an extra byte at the end also.
+                let mut buf = vec![0x00; 1 + max + pec as usize];
+                self.transaction(
+                    address,
+                    &mut [Operation::Write(&[command]), Operation::Read(&mut buf)],
+                )$($await)* ?;
+                unpack_block(pec.then(|| read_pec(address, &[command])), max, &buf)
+            }
+
+            /// 6.5.7, Pg. 42
+            ///
+            /// Block Read of a block with a known size. Exactly `len` data bytes are read after the byte count,
+            /// and the byte count reported by the device must be equal to `len`.
+            $($async)? fn block_read_exact(
+                &mut self,
+                address: A,
+                command: u8,
+                len: usize,
+            ) -> Result<Vec<u8>, SmBusError<Self::Error>> {
+                let (pec, max) = (self.pec_enabled(address), self.max_block_size(address));
+                check_block_len(len, max)?;
+                let mut buf = vec![0x00; 1 + len + pec as usize];
+                self.transaction(
+                    address,
+                    &mut [Operation::Write(&[command]), Operation::Read(&mut buf)],
+                )$($await)* ?;
+                check_byte_count(len, &buf)?;
+                unpack_block(pec.then(|| read_pec(address, &[command])), max, &buf)
+            }
+
+            /// 6.5.8, Pg. 43-44
+            ///
+            /// The write block is followed by a repeated start and the read of the returned block,
+            /// in a single transaction. The size of the returned block is handled as in [`SmBus::block_read`].
+            $($async)? fn block_process_call(
+                &mut self,
+                address: A,
+                command: u8,
+                write_block: &[u8],
+            ) -> Result<Vec<u8>, SmBusError<Self::Error>> {
+                let (pec, max) = (self.pec_enabled(address), self.max_block_size(address));
+                // The PEC is only sent at the end of the transaction, after the read block.
+                let write = pack_block(address, false, max, command, write_block)?;
+                let mut buf = vec![0x00; 1 + max + pec as usize];
+                self.transaction(
+                    address,
+                    &mut [Operation::Write(&write), Operation::Read(&mut buf)],
+                )$($await)* ?;
+                unpack_block(pec.then(|| read_pec(address, &write)), max, &buf)
+            }
+
+            /// 6.5.8, Pg. 43-44
+            ///
+            /// Block Write-Block Read Process Call returning a block with a known size. Exactly `len` data bytes
+            /// are read after the byte count, as in [`SmBus::block_read_exact`].
+            $($async)? fn block_process_call_exact(
+                &mut self,
+                address: A,
+                command: u8,
+                write_block: &[u8],
+                len: usize,
+            ) -> Result<Vec<u8>, SmBusError<Self::Error>> {
+                let (pec, max) = (self.pec_enabled(address), self.max_block_size(address));
+                check_block_len(len, max)?;
+                let write = pack_block(address, false, max, command, write_block)?;
+                let mut buf = vec![0x00; 1 + len + pec as usize];
+                self.transaction(
+                    address,
+                    &mut [Operation::Write(&write), Operation::Read(&mut buf)],
+                )$($await)* ?;
+                check_byte_count(len, &buf)?;
+                unpack_block(pec.then(|| read_pec(address, &write)), max, &buf)
+            }
+
+            /// Read the Alert Response Address, returning the address of a device which asserted `SMBALERT#`.
+            ///
+            /// When several devices are alerting, the one with the lowest address wins arbitration
+            /// and releases `SMBALERT#`. Returns `None` if no device acknowledged the read.
+            $($async)? fn alert_response(&mut self) -> Result<Option<A>, SmBusError<Self::Error>> {
+                match self.receive_byte(A::ALERT_RESPONSE_ADDRESS)$($await)* {
+                    Ok(byte) => Ok(Some(A::from_alert_response(byte))),
+                    Err(SmBusError::Bus(error)) if is_nack(&error) => Ok(None),
+                    Err(error) => Err(error),
+                }
+            }
+
+            /// Read the Alert Response Address until no device responds,
+            /// returning the address of every device which was alerting, in the order they were served.
+            $($async)? fn alert_responses(&mut self) -> Result<Vec<A>, SmBusError<Self::Error>> {
+                let mut addresses = Vec::new();
+                // A device which keeps responding would otherwise keep us here forever.
+                // There cannot be more alerting devices than there are addresses.
+                while addresses.len() < MAX_ALERT_RESPONSES {
+                    match self.alert_response()$($await)* ? {
+                        Some(address) => addresses.push(address),
+                        None => break,
+                    }
+                }
+                Ok(addresses)
+            }
+        }
+    };
+}
+
+pub(crate) use smbus_trait;
+
+smbus_trait! {
+    /// Based on System Management Bus (SMBus) Specification Version 3.2.
+    ///
+    /// <https://smbus.org/specs/SMBus_3_2_20220112.pdf>
+    ///
+    /// The implementation is not comprehensive, and omits several legacy transactions including read/write N-bytes protocols.
+    ///
+    /// Devices which set the `SMBALERT#` bit can be found by reading the Alert Response Address with [`SmBus::alert_response`].
+    /// SMBus Host Notify protocol (6.5.9, Pg. 44), where the device masters the bus to notify the host,
+    /// is received outside of this trait, see [`crate::alert`].
+    ///
+    /// Devices without a fixed address can be enumerated and assigned one through [`arp::Arp`].
+    ///
+    /// Packet Error Checking (6.4, Pg. 34) is opt-in, see [`SmBus::pec_enabled`].
+    /// When enabled, a PEC byte is appended to every write and checked on every read,
+    /// with mismatches reported as [`SmBusError::Pec`].
+    ///
+    /// A blocking counterpart over [`embedded_hal::i2c::I2c`] is provided as [`blocking::SmBus`].
+    #[async_trait::async_trait(?Send)]
+    [async] [.await]
+}
+
+/// Upper bound on the number of reads of the Alert Response Address made by [`SmBus::alert_responses`].
+const MAX_ALERT_RESPONSES: usize = 128;
+
+/// Whether the bus error is the absence of an acknowledgement, as when no device responds.
+fn is_nack<E: Error>(error: &E) -> bool {
+    matches!(error.kind(), ErrorKind::NoAcknowledge(_))
+}
+
+/// Refuse blocks which are longer than `max` bytes.
+fn check_block_len<E>(len: usize, max: usize) -> Result<(), SmBusError<E>> {
+    if len > max {
+        return Err(SmBusError::BlockTooLong { len, max });
     }
+    Ok(())
+}
 
-    /// 6.5.5, Pg. 40-41
-    async fn read_word(&mut self, address: A, command: u8) -> Result<u16, Self::Error> {
-        let mut buf = [0x00, 0x00];
-        self.write_read(address, &[command], &mut buf).await?;
-        Ok(u16::from_le_bytes(buf))
+/// Refuse a block read back with a byte count other than the `len` bytes which were read.
+fn check_byte_count<E>(len: usize, buf: &[u8]) -> Result<(), SmBusError<E>> {
+    if buf[0] as usize != len {
+        return Err(SmBusError::ByteCountMismatch {
+            expected: len,
+            received: buf[0] as usize,
+        });
     }
+    Ok(())
+}
 
-    /// 6.5.6, Pg. 41
-    async fn process_call(
-        &mut self,
-        address: A,
-        command: u8,
-        word: u16,
-    ) -> Result<u16, Self::Error> {
-        let word = word.to_le_bytes();
-        let mut buf = [0x00, 0x00];
-        self.write_read(address, &[command, word[0], word[1]], &mut buf)
-            .await?;
-        Ok(u16::from_le_bytes(buf))
+/// Lay out a block for writing as `[command, count, data @ ..count, pec?]`.
+fn pack_block<A: SmBusAddress, E>(
+    address: A,
+    pec: bool,
+    max: usize,
+    command: u8,
+    block: &[u8],
+) -> Result<Vec<u8>, SmBusError<E>> {
+    check_block_len(block.len(), max)?;
+    let mut buf = Vec::with_capacity(2 + block.len() + pec as usize);
+    buf.extend_from_slice(&[command, block.len() as u8]);
+    buf.extend_from_slice(block);
+    if pec {
+        buf.push(Pec::new().address(address, false).update(&buf).value());
     }
+    Ok(buf)
+}
 
-    /// 6.5.7, Pg. 42
-    async fn block_write(
-        &mut self,
-        address: A,
-        command: u8,
-        block: &[u8],
-    ) -> Result<(), Self::Error> {
-        assert!(block.len() <= SMBUS_MAX_BLOCK_SIZE);
-        self.transaction(
-            address,
-            &mut [
-                Operation::Write(&[command, block.len() as u8]),
-                Operation::Write(block),
-            ],
-        )
-        .await
+/// Validate a block read back from a device, laid out as `[count, data @ ..count, pec?, ..]`.
+/// Any bytes following the block are ignored.
+///
+/// When `pec` is `Some`, it is the PEC state before the byte count was received.
+fn unpack_block<E>(pec: Option<Pec>, max: usize, buf: &[u8]) -> Result<Vec<u8>, SmBusError<E>> {
+    let Some(&count) = buf.first() else {
+        return Err(SmBusError::ByteCountMismatch {
+            expected: 1,
+            received: 0,
+        });
+    };
+    let count = count as usize;
+    check_block_len(count, max)?;
+    if buf.len() < 1 + count + pec.is_some() as usize {
+        return Err(SmBusError::ByteCountMismatch {
+            expected: count,
+            received: buf.len().saturating_sub(1 + pec.is_some() as usize),
+        });
+    }
+    if let Some(pec) = pec {
+        verify(pec, &buf[..=count], buf[1 + count])?;
     }
+    Ok(buf[1..=count].to_vec())
+}
 
-    /// 6.5.7, Pg. 42
-    async fn block_read(&mut self, address: A, command: u8) -> Result<Vec<u8>, Self::Error> {
-        // The first byte is reserved for the size of the data written back.
-        // Later, when PEC is implemented, we will need an extra byte at the end also.
-        let mut buf = Vec::with_capacity(SMBUS_MAX_BLOCK_SIZE + 1);
-        // Figure 37 shows the address byte being sent twice, once for the command write and then again for the read operation.
-        // Currently, `write_read` will perform this in a single transaction, only sending the address once. This might be broken.
-        self.write_read(address, &[command], &mut buf).await?;
-        let (len, block) = (buf[0] as usize, &buf[1..]);
-        assert!(block.len() == std::cmp::min(len, SMBUS_MAX_BLOCK_SIZE));
-        Ok(block.to_vec())
+/// Compute the PEC over all but the last byte of `buf`, which is reserved for the PEC itself.
+///
+/// Returns the bytes to write, omitting the reserved byte when PEC is disabled.
+fn seal<A: SmBusAddress>(address: A, pec: bool, buf: &mut [u8]) -> &[u8] {
+    let len = buf.len() - 1;
+    if !pec {
+        return &buf[..len];
     }
+    buf[len] = Pec::new()
+        .address(address, false)
+        .update(&buf[..len])
+        .value();
+    buf
+}
+
+/// The PEC state of a read transaction, after the bytes written before the repeated start.
+fn read_pec<A: SmBusAddress>(address: A, write: &[u8]) -> Pec {
+    Pec::new()
+        .address(address, false)
+        .update(write)
+        .address(address, true)
+}
 
-    /// 6.5.8, Pg. 43-44
-    async fn block_process_call(
-        &mut self,
-        address: A,
-        command: u8,
-        write_block: &[u8],
-    ) -> Result<Vec<u8>, Self::Error> {
-        assert!(write_block.len() <= SMBUS_MAX_BLOCK_SIZE);
-        let mut buf = Vec::with_capacity(SMBUS_MAX_BLOCK_SIZE + 1);
-        self.transaction(
-            address,
-            &mut [
-                Operation::Write(&[command, write_block.len() as u8]),
-                Operation::Write(write_block),
-                Operation::Read(&mut buf),
-            ],
-        )
-        .await?;
-        let (len, block) = (buf[0] as usize, &buf[1..]);
-        assert!(block.len() == std::cmp::min(len, SMBUS_MAX_BLOCK_SIZE));
-        Ok(block.to_vec())
+/// Check the PEC byte `received` from the device against the PEC computed over `data`.
+fn verify<E>(pec: Pec, data: &[u8], received: u8) -> Result<(), SmBusError<E>> {
+    let expected = pec.update(data).value();
+    if expected == received {
+        Ok(())
+    } else {
+        Err(SmBusError::Pec { expected, received })
     }
 }
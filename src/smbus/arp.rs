@@ -0,0 +1,242 @@
+use embedded_hal_async::i2c::{Operation, SevenBitAddress};
+
+use super::{is_nack, pack_block, read_pec, seal, unpack_block, SmBus, SMBUS_MAX_BLOCK_SIZE};
+use crate::error::SmBusError;
+
+/// The SMBus Device Default Address, to which all ARP commands are sent.
+pub const DEVICE_DEFAULT_ADDRESS: SevenBitAddress = 0x61;
+
+pub const PREPARE_TO_ARP: u8 = 0x01;
+pub const RESET_DEVICE: u8 = 0x02;
+pub const GET_UDID: u8 = 0x03;
+pub const ASSIGN_ADDRESS: u8 = 0x04;
+
+/// Length of the data returned by Get UDID, and written by Assign Address.
+/// This is the UDID followed by one address byte.
+const UDID_BLOCK_SIZE: usize = 17;
+
+/// Unique Device Identifier, which identifies a device during ARP.
+///
+/// The raw bytes are kept as they were received (most significant byte first),
+/// so that a UDID can be written back exactly by [`Arp::assign_address`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Udid(pub [u8; 16]);
+
+/// How a device obtains its address, from the device capabilities field of the UDID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressType {
+    /// The address is fixed, ARP will never change it.
+    Fixed,
+    /// The address is assigned by ARP, and persists across resets.
+    DynamicPersistent,
+    /// The address is assigned by ARP, and is lost on reset.
+    DynamicVolatile,
+    /// The device uses a random number as part of its UDID.
+    RandomNumber,
+}
+
+/// The interface field of the UDID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interface {
+    /// The SMBus version nibble.
+    pub smbus_version: u8,
+    /// The device supports OEM protocol extensions.
+    pub oem: bool,
+    /// The device supports the Alert Standard Format.
+    pub asf: bool,
+    /// The device supports IPMI.
+    pub ipmi: bool,
+    /// The device supports SMBus zones.
+    pub zone: bool,
+}
+
+impl Udid {
+    pub fn address_type(&self) -> AddressType {
+        match self.0[0] >> 6 {
+            0b00 => AddressType::Fixed,
+            0b01 => AddressType::DynamicPersistent,
+            0b10 => AddressType::DynamicVolatile,
+            _ => AddressType::RandomNumber,
+        }
+    }
+
+    pub fn pec_supported(&self) -> bool {
+        self.0[0] & 0x01 != 0
+    }
+
+    /// The version of the UDID format.
+    pub fn udid_version(&self) -> u8 {
+        (self.0[1] >> 3) & 0x07
+    }
+
+    pub fn silicon_revision(&self) -> u8 {
+        self.0[1] & 0x07
+    }
+
+    pub fn vendor_id(&self) -> u16 {
+        u16::from_be_bytes([self.0[2], self.0[3]])
+    }
+
+    pub fn device_id(&self) -> u16 {
+        u16::from_be_bytes([self.0[4], self.0[5]])
+    }
+
+    pub fn interface(&self) -> Interface {
+        let interface = u16::from_be_bytes([self.0[6], self.0[7]]);
+        Interface {
+            smbus_version: (interface & 0x0F) as u8,
+            oem: interface & (1 << 4) != 0,
+            asf: interface & (1 << 5) != 0,
+            ipmi: interface & (1 << 6) != 0,
+            zone: interface & (1 << 7) != 0,
+        }
+    }
+
+    pub fn subsystem_vendor_id(&self) -> u16 {
+        u16::from_be_bytes([self.0[8], self.0[9]])
+    }
+
+    pub fn subsystem_device_id(&self) -> u16 {
+        u16::from_be_bytes([self.0[10], self.0[11]])
+    }
+
+    pub fn vendor_specific_id(&self) -> u32 {
+        u32::from_be_bytes([self.0[12], self.0[13], self.0[14], self.0[15]])
+    }
+}
+
+impl From<[u8; 16]> for Udid {
+    fn from(bytes: [u8; 16]) -> Self {
+        Self(bytes)
+    }
+}
+
+/// A device found by Get UDID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArpDevice {
+    pub udid: Udid,
+    /// The current address of the device, or `None` if it has not been assigned one.
+    pub address: Option<SevenBitAddress>,
+}
+
+impl ArpDevice {
+    fn from_block(block: &[u8]) -> Self {
+        let mut udid = [0x00; 16];
+        udid.copy_from_slice(&block[..16]);
+        // The address is sent in the upper seven bits with the lowest bit set,
+        // or as `0xFF` when no address has been assigned.
+        let address = match block[16] {
+            0xFF => None,
+            byte => Some(byte >> 1),
+        };
+        Self {
+            udid: Udid(udid),
+            address,
+        }
+    }
+}
+
+/// SMBus Address Resolution Protocol (6.6), host side.
+///
+/// Every ARP command is sent to the [`DEVICE_DEFAULT_ADDRESS`], and always uses PEC regardless of [`SmBus::pec_enabled`].
+///
+/// This is implemented for every [`SmBus`] with 7-bit addresses,
+/// so that ARP can be performed with the same bus as [`PmBus`](crate::commands::PmBus).
+#[async_trait::async_trait(?Send)]
+pub trait Arp: SmBus<SevenBitAddress> {
+    /// Prepare to ARP, which clears the Address Resolved flag of every ARP-capable device.
+    async fn prepare_to_arp(&mut self) -> Result<(), SmBusError<Self::Error>> {
+        send_byte(self, PREPARE_TO_ARP).await
+    }
+
+    /// Reset Device (general), which returns every device with a dynamic address to its initial state.
+    async fn reset_device(&mut self) -> Result<(), SmBusError<Self::Error>> {
+        send_byte(self, RESET_DEVICE).await
+    }
+
+    /// Reset Device (directed), which returns the device at `address` to its initial state.
+    async fn reset_device_directed(
+        &mut self,
+        address: SevenBitAddress,
+    ) -> Result<(), SmBusError<Self::Error>> {
+        send_byte(self, address << 1).await
+    }
+
+    /// Get UDID (general), which returns a device whose address has not been resolved.
+    ///
+    /// When several devices respond, the one with the lowest UDID wins arbitration.
+    /// Returns `None` if no device responded.
+    async fn get_udid(&mut self) -> Result<Option<ArpDevice>, SmBusError<Self::Error>> {
+        match block_read(self, GET_UDID).await {
+            Ok(device) => Ok(Some(device)),
+            Err(SmBusError::Bus(error)) if is_nack(&error) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Get UDID (directed), which returns the UDID of the device at `address`.
+    async fn get_udid_directed(
+        &mut self,
+        address: SevenBitAddress,
+    ) -> Result<ArpDevice, SmBusError<Self::Error>> {
+        block_read(self, address << 1 | 1).await
+    }
+
+    /// Assign Address, which gives `address` to the device identified by `udid`.
+    async fn assign_address(
+        &mut self,
+        udid: &Udid,
+        address: SevenBitAddress,
+    ) -> Result<(), SmBusError<Self::Error>> {
+        let mut block = [0x00; UDID_BLOCK_SIZE];
+        block[..16].copy_from_slice(&udid.0);
+        block[16] = address << 1;
+        let buf = pack_block(
+            DEVICE_DEFAULT_ADDRESS,
+            true,
+            SMBUS_MAX_BLOCK_SIZE,
+            ASSIGN_ADDRESS,
+            &block,
+        )?;
+        self.write(DEVICE_DEFAULT_ADDRESS, &buf).await?;
+        Ok(())
+    }
+}
+
+impl<T: SmBus<SevenBitAddress>> Arp for T {}
+
+/// Send Byte to the device default address, always with PEC.
+async fn send_byte<T: SmBus + ?Sized>(
+    bus: &mut T,
+    command: u8,
+) -> Result<(), SmBusError<T::Error>> {
+    let mut buf = [command, 0x00];
+    bus.write(
+        DEVICE_DEFAULT_ADDRESS,
+        seal(DEVICE_DEFAULT_ADDRESS, true, &mut buf),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Block Read of a UDID and address from the device default address, always with PEC.
+async fn block_read<T: SmBus + ?Sized>(
+    bus: &mut T,
+    command: u8,
+) -> Result<ArpDevice, SmBusError<T::Error>> {
+    let mut buf = [0x00; 1 + UDID_BLOCK_SIZE + 1];
+    bus.transaction(
+        DEVICE_DEFAULT_ADDRESS,
+        &mut [Operation::Write(&[command]), Operation::Read(&mut buf)],
+    )
+    .await?;
+    let pec = read_pec(DEVICE_DEFAULT_ADDRESS, &[command]);
+    let block = unpack_block(Some(pec), SMBUS_MAX_BLOCK_SIZE, &buf)?;
+    if block.len() != UDID_BLOCK_SIZE {
+        return Err(SmBusError::ByteCountMismatch {
+            expected: UDID_BLOCK_SIZE,
+            received: block.len(),
+        });
+    }
+    Ok(ArpDevice::from_block(&block))
+}
@@ -0,0 +1,14 @@
+use embedded_hal::i2c::{I2c, Operation, SevenBitAddress};
+
+use super::{
+    check_block_len, check_byte_count, is_nack, pack_block, read_pec, seal, smbus_trait,
+    unpack_block, verify, Pec, SmBusAddress, MAX_ALERT_RESPONSES, SMBUS_MAX_BLOCK_SIZE,
+};
+use crate::error::SmBusError;
+
+smbus_trait! {
+    /// Blocking counterpart of [`SmBus`](super::SmBus), over [`embedded_hal::i2c::I2c`].
+    ///
+    /// The provided methods are generated from the same source as those of the asynchronous trait.
+    [] []
+}
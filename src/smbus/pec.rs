@@ -0,0 +1,49 @@
+use super::SmBusAddress;
+
+/// Polynomial of the CRC-8 used for Packet Error Checking, `x^8 + x^2 + x^1 + 1`.
+///
+/// 6.4, Pg. 34
+pub const PEC_POLYNOMIAL: u8 = 0x07;
+
+/// Running Packet Error Code (CRC-8) computation.
+///
+/// The PEC covers every byte of a transaction as it appears on the wire,
+/// including each address byte (with the R/W bit) and the command code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Pec(u8);
+
+impl Pec {
+    pub const fn new() -> Self {
+        Self(0x00)
+    }
+
+    /// Include an address byte for a write (`read == false`) or a read (`read == true`).
+    #[must_use]
+    pub fn address<A: SmBusAddress>(self, address: A, read: bool) -> Self {
+        self.update(&[address.pec_byte(read)])
+    }
+
+    #[must_use]
+    pub const fn update(self, bytes: &[u8]) -> Self {
+        let mut crc = self.0;
+        let mut i = 0;
+        while i < bytes.len() {
+            crc ^= bytes[i];
+            let mut bit = 0;
+            while bit < 8 {
+                crc = if crc & 0x80 != 0 {
+                    (crc << 1) ^ PEC_POLYNOMIAL
+                } else {
+                    crc << 1
+                };
+                bit += 1;
+            }
+            i += 1;
+        }
+        Self(crc)
+    }
+
+    pub const fn value(self) -> u8 {
+        self.0
+    }
+}
@@ -0,0 +1,423 @@
+//! Status registers, read as sets of named flags.
+//!
+//! Part II, 17
+//!
+//! A flag is set while the condition it reports is present, or until it is cleared if the condition is latched.
+//! Flags are cleared by CLEAR_FAULTS, or by writing the register with the flags to clear set,
+//! see [`PmBusExt::clear`](crate::commands::PmBusExt::clear).
+
+use crate::commands::{
+    STATUS_BYTE, STATUS_CML, STATUS_FANS_1_2, STATUS_FANS_3_4, STATUS_INPUT, STATUS_IOUT,
+    STATUS_OTHER, STATUS_TEMPERATURE, STATUS_VOUT, STATUS_WORD,
+};
+
+/// A status register, which is read as a set of flags and cleared by writing the flags to clear.
+pub trait StatusRegister: Copy {
+    /// The command reading and clearing the register.
+    const COMMAND: u8;
+    /// Whether the register is a word, rather than a byte.
+    const WORD: bool;
+
+    /// The raw value, which is written to clear the flags that are set.
+    fn raw(self) -> u16;
+}
+
+macro_rules! status_flags {
+    (
+        $(#[$attr:meta])*
+        $name:ident($repr:ty) = $command:ident {
+            $(
+                $(#[$flag_attr:meta])*
+                $flag:ident = $bit:literal, $label:literal;
+            )*
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+        pub struct $name(pub $repr);
+
+        impl $name {
+            $(
+                $(#[$flag_attr])*
+                pub const $flag: Self = Self(1 << $bit);
+            )*
+
+            /// Every named flag, with its name in the specification.
+            pub const FLAGS: &'static [(&'static str, Self)] = &[$(($label, Self::$flag)),*];
+
+            pub const fn empty() -> Self {
+                Self(0)
+            }
+
+            pub const fn bits(self) -> $repr {
+                self.0
+            }
+
+            pub const fn is_empty(self) -> bool {
+                self.0 == 0
+            }
+
+            /// Whether every flag set in `other` is set.
+            pub const fn contains(self, other: Self) -> bool {
+                self.0 & other.0 == other.0
+            }
+
+            /// Whether any flag set in `other` is set.
+            pub const fn intersects(self, other: Self) -> bool {
+                self.0 & other.0 != 0
+            }
+
+            /// The names of the flags which are set.
+            pub fn names(self) -> impl Iterator<Item = &'static str> {
+                Self::FLAGS
+                    .iter()
+                    .filter(move |(_, flag)| self.contains(*flag))
+                    .map(|(label, _)| *label)
+            }
+        }
+
+        impl StatusRegister for $name {
+            const COMMAND: u8 = $command;
+            const WORD: bool = core::mem::size_of::<$repr>() == 2;
+
+            fn raw(self) -> u16 {
+                u16::from(self.0)
+            }
+        }
+
+        impl From<$repr> for $name {
+            fn from(bits: $repr) -> Self {
+                Self(bits)
+            }
+        }
+
+        impl From<$name> for $repr {
+            fn from(flags: $name) -> Self {
+                flags.0
+            }
+        }
+
+        impl core::ops::BitOr for $name {
+            type Output = Self;
+
+            fn bitor(self, other: Self) -> Self {
+                Self(self.0 | other.0)
+            }
+        }
+
+        impl core::ops::BitOrAssign for $name {
+            fn bitor_assign(&mut self, other: Self) {
+                self.0 |= other.0;
+            }
+        }
+
+        impl core::ops::BitAnd for $name {
+            type Output = Self;
+
+            fn bitand(self, other: Self) -> Self {
+                Self(self.0 & other.0)
+            }
+        }
+
+        /// Lists the names of the flags which are set, separated by `|`, or `(empty)` if none are.
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                if self.is_empty() {
+                    return f.write_str("(empty)");
+                }
+                let mut names = self.names();
+                if let Some(name) = names.next() {
+                    f.write_str(name)?;
+                }
+                for name in names {
+                    write!(f, " | {name}")?;
+                }
+                let named = Self::FLAGS.iter().fold(0, |named, (_, flag)| named | flag.0);
+                let unnamed = self.0 & !named;
+                if unnamed != 0 {
+                    if unnamed != self.0 {
+                        f.write_str(" | ")?;
+                    }
+                    write!(f, "{unnamed:#X}")?;
+                }
+                Ok(())
+            }
+        }
+
+        impl core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "{}({self})", stringify!($name))
+            }
+        }
+    };
+}
+
+status_flags! {
+    /// The value of STATUS_BYTE, the low byte of [`StatusWord`].
+    StatusByte(u8) = STATUS_BYTE {
+        /// The device was busy and unable to respond.
+        BUSY = 7, "BUSY";
+        /// The output is off, for any reason.
+        OFF = 6, "OFF";
+        VOUT_OV_FAULT = 5, "VOUT_OV_FAULT";
+        IOUT_OC_FAULT = 4, "IOUT_OC_FAULT";
+        VIN_UV_FAULT = 3, "VIN_UV_FAULT";
+        /// A flag of STATUS_TEMPERATURE is set.
+        TEMPERATURE = 2, "TEMPERATURE";
+        /// A flag of STATUS_CML is set.
+        CML = 1, "CML";
+        /// A fault or warning not listed in the other flags of this byte is set.
+        NONE_OF_THE_ABOVE = 0, "NONE_OF_THE_ABOVE";
+    }
+}
+
+status_flags! {
+    /// The value of STATUS_WORD, which summarises the other status registers.
+    ///
+    /// The low byte is the [`StatusByte`].
+    StatusWord(u16) = STATUS_WORD {
+        /// A flag of STATUS_VOUT is set.
+        VOUT = 15, "VOUT";
+        /// A flag of STATUS_IOUT is set.
+        IOUT_POUT = 14, "IOUT/POUT";
+        /// A flag of STATUS_INPUT is set.
+        INPUT = 13, "INPUT";
+        /// A flag of STATUS_MFR_SPECIFIC is set.
+        MFR_SPECIFIC = 12, "MFR_SPECIFIC";
+        /// The POWER_GOOD signal is negated, meaning that power is not good.
+        POWER_GOOD_NEGATED = 11, "POWER_GOOD#";
+        /// A flag of STATUS_FANS_1_2 or STATUS_FANS_3_4 is set.
+        FANS = 10, "FANS";
+        /// A flag of STATUS_OTHER is set.
+        OTHER = 9, "OTHER";
+        /// A fault type not given in the other flags has been detected.
+        UNKNOWN = 8, "UNKNOWN";
+        /// See [`StatusByte::BUSY`].
+        BUSY = 7, "BUSY";
+        /// See [`StatusByte::OFF`].
+        OFF = 6, "OFF";
+        VOUT_OV_FAULT = 5, "VOUT_OV_FAULT";
+        IOUT_OC_FAULT = 4, "IOUT_OC_FAULT";
+        VIN_UV_FAULT = 3, "VIN_UV_FAULT";
+        /// See [`StatusByte::TEMPERATURE`].
+        TEMPERATURE = 2, "TEMPERATURE";
+        /// See [`StatusByte::CML`].
+        CML = 1, "CML";
+        /// See [`StatusByte::NONE_OF_THE_ABOVE`].
+        NONE_OF_THE_ABOVE = 0, "NONE_OF_THE_ABOVE";
+    }
+}
+
+impl StatusWord {
+    /// The low byte, which is the value of STATUS_BYTE.
+    pub const fn status_byte(self) -> StatusByte {
+        StatusByte(self.0 as u8)
+    }
+}
+
+impl From<StatusByte> for StatusWord {
+    fn from(status: StatusByte) -> Self {
+        Self(status.0 as u16)
+    }
+}
+
+status_flags! {
+    /// The value of STATUS_VOUT, reporting on the output voltage.
+    StatusVout(u8) = STATUS_VOUT {
+        VOUT_OV_FAULT = 7, "VOUT_OV_FAULT";
+        VOUT_OV_WARNING = 6, "VOUT_OV_WARNING";
+        VOUT_UV_WARNING = 5, "VOUT_UV_WARNING";
+        VOUT_UV_FAULT = 4, "VOUT_UV_FAULT";
+        /// An attempt was made to set the output voltage above VOUT_MAX or below VOUT_MIN.
+        VOUT_MAX_MIN_WARNING = 3, "VOUT_MAX_MIN_WARNING";
+        /// The output did not reach regulation within TON_MAX_FAULT_LIMIT.
+        TON_MAX_FAULT = 2, "TON_MAX_FAULT";
+        /// The output did not fall within TOFF_MAX_WARN_LIMIT.
+        TOFF_MAX_WARNING = 1, "TOFF_MAX_WARNING";
+        VOUT_TRACKING_ERROR = 0, "VOUT_TRACKING_ERROR";
+    }
+}
+
+status_flags! {
+    /// The value of STATUS_IOUT, reporting on the output current and power.
+    StatusIout(u8) = STATUS_IOUT {
+        IOUT_OC_FAULT = 7, "IOUT_OC_FAULT";
+        /// An overcurrent fault which also caused the output voltage to fall below IOUT_OC_LV_FAULT_LIMIT.
+        IOUT_OC_LV_FAULT = 6, "IOUT_OC_LV_FAULT";
+        IOUT_OC_WARNING = 5, "IOUT_OC_WARNING";
+        IOUT_UC_FAULT = 4, "IOUT_UC_FAULT";
+        CURRENT_SHARE_FAULT = 3, "CURRENT_SHARE_FAULT";
+        IN_POWER_LIMITING_MODE = 2, "IN_POWER_LIMITING_MODE";
+        POUT_OP_FAULT = 1, "POUT_OP_FAULT";
+        POUT_OP_WARNING = 0, "POUT_OP_WARNING";
+    }
+}
+
+status_flags! {
+    /// The value of STATUS_INPUT, reporting on the input voltage, current and power.
+    StatusInput(u8) = STATUS_INPUT {
+        VIN_OV_FAULT = 7, "VIN_OV_FAULT";
+        VIN_OV_WARNING = 6, "VIN_OV_WARNING";
+        VIN_UV_WARNING = 5, "VIN_UV_WARNING";
+        VIN_UV_FAULT = 4, "VIN_UV_FAULT";
+        /// The unit is off because the input voltage is below VIN_ON or VIN_OFF.
+        UNIT_OFF_LOW_INPUT = 3, "UNIT_OFF_LOW_INPUT";
+        IIN_OC_FAULT = 2, "IIN_OC_FAULT";
+        IIN_OC_WARNING = 1, "IIN_OC_WARNING";
+        PIN_OP_WARNING = 0, "PIN_OP_WARNING";
+    }
+}
+
+status_flags! {
+    /// The value of STATUS_TEMPERATURE. Bits 3 to 0 are reserved.
+    StatusTemperature(u8) = STATUS_TEMPERATURE {
+        OT_FAULT = 7, "OT_FAULT";
+        OT_WARNING = 6, "OT_WARNING";
+        UT_WARNING = 5, "UT_WARNING";
+        UT_FAULT = 4, "UT_FAULT";
+    }
+}
+
+status_flags! {
+    /// The value of STATUS_CML, reporting communication, memory and logic faults. Bit 2 is reserved.
+    StatusCml(u8) = STATUS_CML {
+        /// An invalid or unsupported command was received.
+        INVALID_COMMAND = 7, "INVALID_COMMAND";
+        /// Invalid or unsupported data was received.
+        INVALID_DATA = 6, "INVALID_DATA";
+        /// The Packet Error Code of a received packet was incorrect.
+        PEC_FAILED = 5, "PEC_FAILED";
+        MEMORY_FAULT = 4, "MEMORY_FAULT";
+        PROCESSOR_FAULT = 3, "PROCESSOR_FAULT";
+        /// A communication fault other than those above.
+        OTHER_COMMUNICATION_FAULT = 1, "OTHER_COMMUNICATION_FAULT";
+        /// A memory or logic fault other than those above.
+        OTHER_MEMORY_OR_LOGIC_FAULT = 0, "OTHER_MEMORY_OR_LOGIC_FAULT";
+    }
+}
+
+status_flags! {
+    /// The value of STATUS_OTHER. Bits 7 and 6 are reserved.
+    StatusOther(u8) = STATUS_OTHER {
+        INPUT_A_FUSE_FAULT = 5, "INPUT_A_FUSE_FAULT";
+        INPUT_B_FUSE_FAULT = 4, "INPUT_B_FUSE_FAULT";
+        INPUT_A_ORING_FAULT = 3, "INPUT_A_ORING_FAULT";
+        INPUT_B_ORING_FAULT = 2, "INPUT_B_ORING_FAULT";
+        OUTPUT_ORING_FAULT = 1, "OUTPUT_ORING_FAULT";
+        /// This device was the first on the bus to assert `SMBALERT#`.
+        FIRST_TO_ASSERT_SMBALERT = 0, "FIRST_TO_ASSERT_SMBALERT";
+    }
+}
+
+status_flags! {
+    /// The value of STATUS_FANS_1_2, reporting on fans 1 and 2 and the airflow.
+    StatusFans12(u8) = STATUS_FANS_1_2 {
+        FAN_1_FAULT = 7, "FAN_1_FAULT";
+        FAN_2_FAULT = 6, "FAN_2_FAULT";
+        FAN_1_WARNING = 5, "FAN_1_WARNING";
+        FAN_2_WARNING = 4, "FAN_2_WARNING";
+        /// The speed of fan 1 has been overridden, such as by the host through FAN_COMMAND_1.
+        FAN_1_SPEED_OVERRIDDEN = 3, "FAN_1_SPEED_OVERRIDDEN";
+        /// The speed of fan 2 has been overridden, such as by the host through FAN_COMMAND_2.
+        FAN_2_SPEED_OVERRIDDEN = 2, "FAN_2_SPEED_OVERRIDDEN";
+        AIRFLOW_FAULT = 1, "AIRFLOW_FAULT";
+        AIRFLOW_WARNING = 0, "AIRFLOW_WARNING";
+    }
+}
+
+status_flags! {
+    /// The value of STATUS_FANS_3_4, reporting on fans 3 and 4. Bits 1 and 0 are reserved.
+    StatusFans34(u8) = STATUS_FANS_3_4 {
+        FAN_3_FAULT = 7, "FAN_3_FAULT";
+        FAN_4_FAULT = 6, "FAN_4_FAULT";
+        FAN_3_WARNING = 5, "FAN_3_WARNING";
+        FAN_4_WARNING = 4, "FAN_4_WARNING";
+        /// The speed of fan 3 has been overridden, such as by the host through FAN_COMMAND_3.
+        FAN_3_SPEED_OVERRIDDEN = 3, "FAN_3_SPEED_OVERRIDDEN";
+        /// The speed of fan 4 has been overridden, such as by the host through FAN_COMMAND_4.
+        FAN_4_SPEED_OVERRIDDEN = 2, "FAN_4_SPEED_OVERRIDDEN";
+    }
+}
+
+/// The status of one page, with the sub-status registers indicated by its STATUS_WORD,
+/// as read by [`PmBusExt::read_fault_reports`](crate::commands::PmBusExt::read_fault_reports).
+///
+/// Sub-status registers are `None` when STATUS_WORD does not indicate them, and so they were not read.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FaultReport {
+    pub page: u8,
+    pub status: StatusWord,
+    pub vout: Option<StatusVout>,
+    pub iout: Option<StatusIout>,
+    pub input: Option<StatusInput>,
+    pub temperature: Option<StatusTemperature>,
+    pub cml: Option<StatusCml>,
+    pub other: Option<StatusOther>,
+    /// STATUS_MFR_SPECIFIC, whose bits are defined by the manufacturer.
+    pub mfr_specific: Option<u8>,
+    pub fans_1_2: Option<StatusFans12>,
+    /// Read along with STATUS_FANS_1_2, but `None` if the device does not support it,
+    /// as devices with fewer than three fans need not.
+    pub fans_3_4: Option<StatusFans34>,
+}
+
+impl FaultReport {
+    /// The summary flags of [`StatusWord`] indicating STATUS_VOUT.
+    pub const VOUT: StatusWord = StatusWord(StatusWord::VOUT.0 | StatusWord::VOUT_OV_FAULT.0);
+    /// The summary flags of [`StatusWord`] indicating STATUS_IOUT.
+    pub const IOUT: StatusWord = StatusWord(StatusWord::IOUT_POUT.0 | StatusWord::IOUT_OC_FAULT.0);
+    /// The summary flags of [`StatusWord`] indicating STATUS_INPUT.
+    pub const INPUT: StatusWord = StatusWord(StatusWord::INPUT.0 | StatusWord::VIN_UV_FAULT.0);
+
+    /// A report of `status`, before any sub-status registers are read.
+    pub const fn new(page: u8, status: StatusWord) -> Self {
+        Self {
+            page,
+            status,
+            vout: None,
+            iout: None,
+            input: None,
+            temperature: None,
+            cml: None,
+            other: None,
+            mfr_specific: None,
+            fans_1_2: None,
+            fans_3_4: None,
+        }
+    }
+
+    /// Whether nothing at all is reported.
+    pub const fn is_clear(&self) -> bool {
+        self.status.is_empty()
+    }
+}
+
+/// Lists the flags of STATUS_WORD, followed by the flags of each sub-status register read on its own line.
+impl core::fmt::Display for FaultReport {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fn line(
+            f: &mut core::fmt::Formatter<'_>,
+            command: &str,
+            flags: Option<impl core::fmt::Display>,
+        ) -> core::fmt::Result {
+            match flags {
+                Some(flags) => write!(f, "\n  {command}: {flags}"),
+                None => Ok(()),
+            }
+        }
+        write!(f, "page {}: {}", self.page, self.status)?;
+        line(f, "STATUS_VOUT", self.vout)?;
+        line(f, "STATUS_IOUT", self.iout)?;
+        line(f, "STATUS_INPUT", self.input)?;
+        line(f, "STATUS_TEMPERATURE", self.temperature)?;
+        line(f, "STATUS_CML", self.cml)?;
+        line(f, "STATUS_OTHER", self.other)?;
+        line(
+            f,
+            "STATUS_MFR_SPECIFIC",
+            self.mfr_specific.map(|bits| format!("{bits:#04X}")),
+        )?;
+        line(f, "STATUS_FANS_1_2", self.fans_1_2)?;
+        line(f, "STATUS_FANS_3_4", self.fans_3_4)
+    }
+}
@@ -0,0 +1,89 @@
+//! Physical quantities, in the units used by PMBus commands.
+//!
+//! Part II, 7.1
+//!
+//! Commands declare their unit in the command table, and are read and written as these types.
+//! The numeric format of the data on the bus is resolved by [`PmBus`](crate::commands::PmBus).
+
+/// A physical quantity, held as an `f64` in its unit.
+pub trait Quantity: Copy + From<f64> + Into<f64> {
+    /// The symbol of the unit, used when displaying a quantity.
+    const SYMBOL: &'static str;
+}
+
+macro_rules! quantity {
+    ($(#[$attr:meta])* $name:ident, $symbol:literal) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+        pub struct $name(pub f64);
+
+        impl Quantity for $name {
+            const SYMBOL: &'static str = $symbol;
+        }
+
+        impl From<f64> for $name {
+            fn from(value: f64) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for f64 {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                self.0.fmt(f)?;
+                write!(f, " {}", Self::SYMBOL)
+            }
+        }
+    };
+}
+
+quantity!(
+    /// Electric potential, in volts.
+    Volts,
+    "V"
+);
+quantity!(
+    /// Electric current, in amperes.
+    Amps,
+    "A"
+);
+quantity!(
+    /// Power, in watts.
+    Watts,
+    "W"
+);
+quantity!(
+    /// Temperature, in degrees Celsius.
+    Celsius,
+    "°C"
+);
+quantity!(
+    /// Rotational speed, in revolutions per minute.
+    Rpm,
+    "RPM"
+);
+quantity!(
+    /// A proportion, in percent.
+    Percent,
+    "%"
+);
+quantity!(
+    /// Frequency, in hertz.
+    Hertz,
+    "Hz"
+);
+quantity!(
+    /// Time, in milliseconds.
+    Milliseconds,
+    "ms"
+);
+quantity!(
+    /// Energy, in joules.
+    Joules,
+    "J"
+);
@@ -0,0 +1,146 @@
+mod common;
+
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+use embedded_hal_mock::eh1::i2c::Transaction;
+use std::collections::VecDeque;
+
+use embedded_hal::i2c::ErrorType;
+use pmbus::alert::{wait_for_alerts, wait_for_host_notify, Alert, HostNotifyTarget};
+use pmbus::error::SmBusError;
+use pmbus::smbus::{Pec, SmBus, ALERT_RESPONSE_ADDRESS};
+use pollster::block_on;
+
+use self::common::Bus;
+
+fn nack() -> Transaction {
+    Transaction::read(ALERT_RESPONSE_ADDRESS, vec![0x00])
+        .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))
+}
+
+#[test]
+fn alert_response_none() {
+    let mut bus = Bus::new(&[nack()]);
+    assert_eq!(block_on(bus.alert_response()).unwrap(), None);
+    bus.done();
+}
+
+#[test]
+fn alert_response_with_pec() {
+    let pec = Pec::new()
+        .address(ALERT_RESPONSE_ADDRESS, true)
+        .update(&[0x58 << 1])
+        .value();
+    let mut bus = Bus::with_pec(&[Transaction::read(
+        ALERT_RESPONSE_ADDRESS,
+        vec![0x58 << 1, pec],
+    )]);
+    assert_eq!(block_on(bus.alert_response()).unwrap(), Some(0x58));
+    bus.done();
+}
+
+#[test]
+fn wait_for_alerts_serves_every_device() {
+    let mut bus = Bus::new(&[
+        Transaction::read(ALERT_RESPONSE_ADDRESS, vec![0x40 << 1]),
+        Transaction::read(ALERT_RESPONSE_ADDRESS, vec![0x58 << 1 | 1]),
+        nack(),
+    ]);
+    let mut smbalert = PinMock::new(&[PinTransaction::wait_for_state(State::Low)]);
+    let alerts = block_on(wait_for_alerts(&mut bus, &mut smbalert)).unwrap();
+    assert_eq!(
+        alerts,
+        [
+            Alert::Smbalert { address: 0x40 },
+            Alert::Smbalert { address: 0x58 }
+        ]
+    );
+    bus.done();
+    smbalert.done();
+}
+
+/// Plays back Host Notify messages, as if received in target mode.
+struct Target(VecDeque<Result<Vec<u8>, ErrorKind>>);
+
+impl ErrorType for Target {
+    type Error = ErrorKind;
+}
+
+#[async_trait::async_trait(?Send)]
+impl HostNotifyTarget for Target {
+    async fn receive(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let message = self
+            .0
+            .pop_front()
+            .expect("no pending host notify message")?;
+        let len = message.len().min(buf.len());
+        buf[..len].copy_from_slice(&message[..len]);
+        Ok(message.len())
+    }
+}
+
+#[test]
+fn host_notify_parse() {
+    let alert = Alert::from_host_notify::<ErrorKind>(&[0x58 << 1, 0x34, 0x12]).unwrap();
+    assert_eq!(
+        alert,
+        Alert::HostNotify {
+            address: 0x58,
+            data: 0x1234
+        }
+    );
+    assert_eq!(alert.address(), 0x58);
+}
+
+#[test]
+fn host_notify_messages() {
+    let mut target = Target(VecDeque::from([
+        Ok(vec![0x40 << 1, 0x01, 0x00]),
+        Ok(vec![0x41 << 1, 0xFF, 0xFF]),
+    ]));
+    assert_eq!(
+        block_on(wait_for_host_notify(&mut target)).unwrap(),
+        Alert::HostNotify {
+            address: 0x40,
+            data: 0x0001
+        }
+    );
+    assert_eq!(
+        block_on(wait_for_host_notify(&mut target)).unwrap(),
+        Alert::HostNotify {
+            address: 0x41,
+            data: 0xFFFF
+        }
+    );
+}
+
+#[test]
+fn host_notify_wrong_length() {
+    let mut target = Target(VecDeque::from([
+        Ok(vec![0x40 << 1, 0x01]),
+        Ok(vec![0x40 << 1, 0x01, 0x00, 0x00]),
+    ]));
+    assert_eq!(
+        block_on(wait_for_host_notify(&mut target)).unwrap_err(),
+        SmBusError::ByteCountMismatch {
+            expected: 3,
+            received: 2
+        }
+    );
+    assert_eq!(
+        block_on(wait_for_host_notify(&mut target)).unwrap_err(),
+        SmBusError::ByteCountMismatch {
+            expected: 3,
+            received: 4
+        }
+    );
+}
+
+#[test]
+fn host_notify_target_error() {
+    let mut target = Target(VecDeque::from([Err(ErrorKind::Bus)]));
+    assert_eq!(
+        block_on(wait_for_host_notify(&mut target)).unwrap_err(),
+        SmBusError::Bus(ErrorKind::Bus)
+    );
+}
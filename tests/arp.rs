@@ -0,0 +1,115 @@
+mod common;
+
+use embedded_hal_mock::eh1::i2c::Transaction;
+use pmbus::smbus::arp::{AddressType, Arp, Udid, DEVICE_DEFAULT_ADDRESS};
+use pmbus::smbus::Pec;
+use pollster::block_on;
+
+use self::common::Bus;
+
+const UDID: [u8; 16] = [
+    0x41, 0x0A, 0x12, 0x34, 0x56, 0x78, 0x00, 0x24, 0x9A, 0xBC, 0xDE, 0xF0, 0x01, 0x02, 0x03, 0x04,
+];
+
+fn get_udid_response(command: u8, address: u8) -> Vec<u8> {
+    let mut response = vec![17];
+    response.extend(UDID);
+    response.push(address);
+    let pec = Pec::new()
+        .address(DEVICE_DEFAULT_ADDRESS, false)
+        .update(&[command])
+        .address(DEVICE_DEFAULT_ADDRESS, true)
+        .update(&response)
+        .value();
+    response.push(pec);
+    response
+}
+
+#[test]
+fn udid_fields() {
+    let udid = Udid(UDID);
+    assert_eq!(udid.address_type(), AddressType::DynamicPersistent);
+    assert!(udid.pec_supported());
+    assert_eq!(udid.udid_version(), 1);
+    assert_eq!(udid.silicon_revision(), 2);
+    assert_eq!(udid.vendor_id(), 0x1234);
+    assert_eq!(udid.device_id(), 0x5678);
+    let interface = udid.interface();
+    assert_eq!(interface.smbus_version, 4);
+    assert!(interface.asf);
+    assert!(!interface.oem && !interface.ipmi && !interface.zone);
+    assert_eq!(udid.subsystem_vendor_id(), 0x9ABC);
+    assert_eq!(udid.subsystem_device_id(), 0xDEF0);
+    assert_eq!(udid.vendor_specific_id(), 0x01020304);
+}
+
+#[test]
+fn prepare_to_arp_always_uses_pec() {
+    let pec = Pec::new()
+        .address(DEVICE_DEFAULT_ADDRESS, false)
+        .update(&[0x01])
+        .value();
+    let mut bus = Bus::new(&[Transaction::write(DEVICE_DEFAULT_ADDRESS, vec![0x01, pec])]);
+    block_on(bus.prepare_to_arp()).unwrap();
+    bus.done();
+}
+
+#[test]
+fn reset_device_directed() {
+    let pec = Pec::new()
+        .address(DEVICE_DEFAULT_ADDRESS, false)
+        .update(&[0x40 << 1])
+        .value();
+    let mut bus = Bus::new(&[Transaction::write(
+        DEVICE_DEFAULT_ADDRESS,
+        vec![0x40 << 1, pec],
+    )]);
+    block_on(bus.reset_device_directed(0x40)).unwrap();
+    bus.done();
+}
+
+#[test]
+fn get_udid_unassigned() {
+    let mut bus = Bus::new(&[
+        Transaction::transaction_start(DEVICE_DEFAULT_ADDRESS),
+        Transaction::write(DEVICE_DEFAULT_ADDRESS, vec![0x03]),
+        Transaction::read(DEVICE_DEFAULT_ADDRESS, get_udid_response(0x03, 0xFF)),
+        Transaction::transaction_end(DEVICE_DEFAULT_ADDRESS),
+    ]);
+    let device = block_on(bus.get_udid()).unwrap().unwrap();
+    assert_eq!(device.udid, Udid(UDID));
+    assert_eq!(device.address, None);
+    bus.done();
+}
+
+#[test]
+fn get_udid_directed() {
+    let command = 0x40 << 1 | 1;
+    let mut bus = Bus::new(&[
+        Transaction::transaction_start(DEVICE_DEFAULT_ADDRESS),
+        Transaction::write(DEVICE_DEFAULT_ADDRESS, vec![command]),
+        Transaction::read(
+            DEVICE_DEFAULT_ADDRESS,
+            get_udid_response(command, 0x40 << 1 | 1),
+        ),
+        Transaction::transaction_end(DEVICE_DEFAULT_ADDRESS),
+    ]);
+    let device = block_on(bus.get_udid_directed(0x40)).unwrap();
+    assert_eq!(device.address, Some(0x40));
+    bus.done();
+}
+
+#[test]
+fn assign_address() {
+    let mut write = vec![0x04, 17];
+    write.extend(UDID);
+    write.push(0x40 << 1);
+    let pec = Pec::new()
+        .address(DEVICE_DEFAULT_ADDRESS, false)
+        .update(&write)
+        .value();
+    write.push(pec);
+    let mut bus = Bus::new(&[Transaction::write(DEVICE_DEFAULT_ADDRESS, write)]);
+    block_on(bus.assign_address(&Udid(UDID), 0x40)).unwrap();
+    bus.done();
+}
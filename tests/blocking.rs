@@ -0,0 +1,63 @@
+mod common;
+
+use embedded_hal_mock::eh1::i2c::Transaction;
+use pmbus::commands::blocking::PmBus;
+use pmbus::format::Linear11;
+use pmbus::smbus::blocking::SmBus;
+use pmbus::smbus::Pec;
+use pmbus::units::Volts;
+
+use self::common::{padded, Bus};
+
+const ADDRESS: u8 = 0x58;
+
+#[test]
+fn read_word_with_pec() {
+    let pec = Pec::new()
+        .address(ADDRESS, false)
+        .update(&[0x8B])
+        .address(ADDRESS, true)
+        .update(&[0x34, 0x12])
+        .value();
+    let mut bus = Bus::with_pec(&[Transaction::write_read(
+        ADDRESS,
+        vec![0x8B],
+        vec![0x34, 0x12, pec],
+    )]);
+    assert_eq!(SmBus::read_word(&mut bus, ADDRESS, 0x8B).unwrap(), 0x1234);
+    bus.done();
+}
+
+#[test]
+fn block_read_matches_async() {
+    let mut bus = Bus::new(&[
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![0x99]),
+        Transaction::read(ADDRESS, padded(&[2, b'F', b'E'], false)),
+        Transaction::transaction_end(ADDRESS),
+    ]);
+    assert_eq!(SmBus::block_read(&mut bus, ADDRESS, 0x99).unwrap(), b"FE");
+    bus.done();
+}
+
+#[test]
+fn generated_commands() {
+    let mut bus = Bus::new(&[
+        Transaction::write(ADDRESS, vec![0x03]),
+        Transaction::write_read(ADDRESS, vec![0x88], vec![0xCD, 0xAB]),
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![0x86]),
+        Transaction::read(ADDRESS, vec![6, 1, 2, 3, 4, 5, 6]),
+        Transaction::transaction_end(ADDRESS),
+    ]);
+    bus.write_clear_faults(ADDRESS).unwrap();
+    assert_eq!(
+        bus.read_read_vin(ADDRESS).unwrap(),
+        Volts(Linear11(0xABCD).to_f64())
+    );
+    assert_eq!(
+        <[u8; 6]>::from(bus.read_read_ein(ADDRESS).unwrap()),
+        [1, 2, 3, 4, 5, 6]
+    );
+    bus.done();
+}
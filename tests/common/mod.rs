@@ -0,0 +1,202 @@
+#![allow(dead_code)]
+
+use embedded_hal::i2c::{ErrorKind, ErrorType, Operation};
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+use pmbus::cache::Cache;
+use pmbus::format::{Coefficients, VidCode};
+use pmbus::smbus::SMBUS_MAX_BLOCK_SIZE;
+
+/// Wraps the mock bus so that `SmBus` can be implemented for it.
+pub struct Bus {
+    i2c: Mock,
+    pec: bool,
+    max_block_size: usize,
+    cache: Option<Cache>,
+    fixed_coefficients: Vec<(u8, bool, Coefficients)>,
+    vid_tables: Vec<(u8, VidCode)>,
+}
+
+impl Bus {
+    pub fn new(expectations: &[Transaction]) -> Self {
+        Self {
+            i2c: Mock::new(expectations),
+            pec: false,
+            max_block_size: SMBUS_MAX_BLOCK_SIZE,
+            cache: None,
+            fixed_coefficients: Vec::new(),
+            vid_tables: Vec::new(),
+        }
+    }
+
+    pub fn with_pec(expectations: &[Transaction]) -> Self {
+        Self {
+            pec: true,
+            ..Self::new(expectations)
+        }
+    }
+
+    pub fn with_max_block_size(self, max_block_size: usize) -> Self {
+        Self {
+            max_block_size,
+            ..self
+        }
+    }
+
+    pub fn with_cache(self) -> Self {
+        Self {
+            cache: Some(Cache::new()),
+            ..self
+        }
+    }
+
+    pub fn with_fixed_coefficients(
+        mut self,
+        command: u8,
+        read: bool,
+        coefficients: Coefficients,
+    ) -> Self {
+        self.fixed_coefficients.push((command, read, coefficients));
+        self
+    }
+
+    /// Select `table` by the VID code type `code_type`, as a datasheet would.
+    pub fn with_vid_table(mut self, code_type: u8, table: VidCode) -> Self {
+        self.vid_tables.push((code_type, table));
+        self
+    }
+
+    fn find_vid_table(&self, code_type: u8) -> Option<VidCode> {
+        self.vid_tables
+            .iter()
+            .find(|vid| vid.0 == code_type)
+            .map(|vid| vid.1)
+    }
+
+    fn find_fixed_coefficients(&self, command: u8, read: bool) -> Option<Coefficients> {
+        self.fixed_coefficients
+            .iter()
+            .find(|fixed| (fixed.0, fixed.1) == (command, read))
+            .map(|fixed| fixed.2)
+    }
+
+    pub fn cache(&self) -> Option<&Cache> {
+        self.cache.as_ref()
+    }
+
+    pub fn done(mut self) {
+        self.i2c.done();
+    }
+}
+
+impl ErrorType for Bus {
+    type Error = ErrorKind;
+}
+
+impl embedded_hal_async::i2c::I2c for Bus {
+    async fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        embedded_hal_async::i2c::I2c::read(&mut self.i2c, address, read).await
+    }
+
+    async fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        embedded_hal_async::i2c::I2c::write(&mut self.i2c, address, write).await
+    }
+
+    async fn write_read(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        embedded_hal_async::i2c::I2c::write_read(&mut self.i2c, address, write, read).await
+    }
+
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        embedded_hal_async::i2c::I2c::transaction(&mut self.i2c, address, operations).await
+    }
+}
+
+impl embedded_hal::i2c::I2c for Bus {
+    fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        embedded_hal::i2c::I2c::read(&mut self.i2c, address, read)
+    }
+
+    fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        embedded_hal::i2c::I2c::write(&mut self.i2c, address, write)
+    }
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        embedded_hal::i2c::I2c::write_read(&mut self.i2c, address, write, read)
+    }
+
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        embedded_hal::i2c::I2c::transaction(&mut self.i2c, address, operations)
+    }
+}
+
+impl pmbus::smbus::SmBus for Bus {
+    fn pec_enabled(&self, _address: u8) -> bool {
+        self.pec
+    }
+
+    fn max_block_size(&self, _address: u8) -> usize {
+        self.max_block_size
+    }
+}
+
+impl pmbus::smbus::blocking::SmBus for Bus {
+    fn pec_enabled(&self, _address: u8) -> bool {
+        self.pec
+    }
+
+    fn max_block_size(&self, _address: u8) -> usize {
+        self.max_block_size
+    }
+}
+
+impl pmbus::commands::PmBus for Bus {
+    fn cache(&mut self) -> Option<&mut Cache> {
+        self.cache.as_mut()
+    }
+
+    fn fixed_coefficients(&self, _address: u8, command: u8, read: bool) -> Option<Coefficients> {
+        self.find_fixed_coefficients(command, read)
+    }
+
+    fn vid_table(&self, _address: u8, code_type: u8) -> Option<VidCode> {
+        self.find_vid_table(code_type)
+    }
+}
+
+impl pmbus::commands::blocking::PmBus for Bus {
+    fn cache(&mut self) -> Option<&mut Cache> {
+        self.cache.as_mut()
+    }
+
+    fn fixed_coefficients(&self, _address: u8, command: u8, read: bool) -> Option<Coefficients> {
+        self.find_fixed_coefficients(command, read)
+    }
+
+    fn vid_table(&self, _address: u8, code_type: u8) -> Option<VidCode> {
+        self.find_vid_table(code_type)
+    }
+}
+
+/// Pad a response out to the length of the read buffer used for blocks of unknown size.
+pub fn padded(response: &[u8], pec: bool) -> Vec<u8> {
+    let mut padded = response.to_vec();
+    padded.resize(1 + SMBUS_MAX_BLOCK_SIZE + pec as usize, 0xFF);
+    padded
+}
@@ -0,0 +1,167 @@
+mod common;
+
+use embedded_hal_mock::eh1::i2c::Transaction;
+use pmbus::commands::{
+    PmBus, PmBusExt, COEFFICIENTS, PAGE, READ_IOUT, READ_VOUT, VOUT_COMMAND, VOUT_MODE,
+};
+use pmbus::error::{FormatError, PmBusError};
+use pmbus::format::{Coefficients, Direct};
+use pmbus::sim::{SimulatedBus, SimulatedDevice};
+use pmbus::units::Volts;
+use pollster::block_on;
+
+use self::common::Bus;
+
+const ADDRESS: u8 = 0x40;
+
+/// `X = Y·10^-3`, as for a voltage in millivolts.
+const MILLI: Coefficients = Coefficients::new(1, 0, 3);
+
+/// COEFFICIENTS request for `command`, answered with [`MILLI`].
+fn coefficients_call(command: u8, read: bool) -> [Transaction; 4] {
+    [
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![COEFFICIENTS, 2, command, read as u8]),
+        Transaction::read(ADDRESS, vec![5, 0x01, 0x00, 0x00, 0x00, 0x03]),
+        Transaction::transaction_end(ADDRESS),
+    ]
+}
+
+#[test]
+fn direct_codec() {
+    assert_eq!(Direct(1200).to_f64(MILLI).unwrap(), 1.2);
+    assert_eq!(Direct::from_f64(1.2, MILLI), Ok(Direct(1200)));
+    assert_eq!(Direct(0xFC18).to_f64(MILLI).unwrap(), -1.0);
+
+    let coefficients = Coefficients::new(25, 20, -1);
+    assert_eq!(Direct(50).to_f64(coefficients).unwrap(), 19.2);
+    assert_eq!(Direct::from_f64(19.2, coefficients), Ok(Direct(50)));
+
+    assert_eq!(Direct::from_f64(40.0, MILLI), Err(FormatError::OutOfRange));
+    assert_eq!(
+        Direct::from_f64(f64::NAN, MILLI),
+        Err(FormatError::NotFinite)
+    );
+    for y in [i16::MIN, -1, 0, 1, i16::MAX] {
+        let direct = Direct(y as u16);
+        assert_eq!(
+            Direct::from_f64(direct.to_f64(MILLI).unwrap(), MILLI),
+            Ok(direct)
+        );
+    }
+}
+
+#[test]
+fn zero_m_is_invalid() {
+    let zero = Coefficients::new(0, 5, 0);
+    assert_eq!(Direct(1200).to_f64(zero), Err(FormatError::Invalid));
+    assert_eq!(Direct(1200).to_f32(zero), Err(FormatError::Invalid));
+    assert_eq!(Direct::from_f64(1.2, zero), Err(FormatError::Invalid));
+
+    let mut bus = Bus::new(&[
+        Transaction::write_read(ADDRESS, vec![READ_VOUT], vec![0xB0, 0x04]),
+        Transaction::write_read(ADDRESS, vec![VOUT_MODE], vec![0x40]),
+    ])
+    .with_fixed_coefficients(READ_VOUT, true, zero);
+    assert_eq!(
+        block_on(bus.read_read_vout(ADDRESS)),
+        Err(PmBusError::Format(FormatError::Invalid))
+    );
+    bus.done();
+}
+
+#[test]
+fn coefficients_bytes() {
+    let coefficients = Coefficients::new(-2, 0x1234, -3);
+    let bytes: [u8; 5] = coefficients.into();
+    assert_eq!(bytes, [0xFE, 0xFF, 0x34, 0x12, 0xFD]);
+    assert_eq!(Coefficients::from(bytes), coefficients);
+}
+
+#[test]
+fn call_coefficients() {
+    let mut bus = Bus::new(&coefficients_call(READ_VOUT, true));
+    assert_eq!(
+        block_on(bus.call_coefficients(ADDRESS, [READ_VOUT, 1])).unwrap(),
+        MILLI
+    );
+    bus.done();
+}
+
+#[test]
+fn call_coefficients_wrong_size() {
+    let mut bus = Bus::new(&[
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![COEFFICIENTS, 2, READ_VOUT, 1]),
+        Transaction::read(ADDRESS, vec![4, 0x01, 0x00, 0x00, 0x00, 0xFF]),
+        Transaction::transaction_end(ADDRESS),
+    ]);
+    assert!(matches!(
+        block_on(bus.call_coefficients(ADDRESS, [READ_VOUT, 1])).unwrap_err(),
+        PmBusError::ByteCountMismatch { .. }
+    ));
+    bus.done();
+}
+
+#[test]
+fn coefficients_cached_per_page() {
+    let mut expectations = vec![Transaction::write_read(ADDRESS, vec![PAGE], vec![0])];
+    expectations.extend(coefficients_call(READ_IOUT, true));
+    expectations.push(Transaction::write(ADDRESS, vec![PAGE, 1]));
+    expectations.extend(coefficients_call(READ_IOUT, true));
+    let mut bus = Bus::new(&expectations).with_cache();
+
+    for _ in 0..2 {
+        assert_eq!(
+            block_on(bus.coefficients(ADDRESS, READ_IOUT, true)).unwrap(),
+            MILLI
+        );
+    }
+    block_on(bus.send_page(ADDRESS, 1)).unwrap();
+    assert_eq!(
+        block_on(bus.coefficients(ADDRESS, READ_IOUT, true)).unwrap(),
+        MILLI
+    );
+    assert_eq!(
+        bus.cache()
+            .unwrap()
+            .coefficients(ADDRESS, 0, READ_IOUT, true),
+        Some(MILLI)
+    );
+    assert_eq!(
+        bus.cache()
+            .unwrap()
+            .coefficients(ADDRESS, 1, READ_IOUT, false),
+        None
+    );
+    bus.done();
+}
+
+#[test]
+fn fixed_coefficients() {
+    let mut bus = Bus::new(&[
+        Transaction::write_read(ADDRESS, vec![READ_VOUT], vec![0xB0, 0x04]),
+        Transaction::write_read(ADDRESS, vec![VOUT_MODE], vec![0x40]),
+    ])
+    .with_fixed_coefficients(READ_VOUT, true, MILLI);
+    assert_eq!(block_on(bus.read_read_vout(ADDRESS)).unwrap(), Volts(1.2));
+    bus.done();
+}
+
+#[test]
+fn vout_direct() {
+    let device = SimulatedDevice::new(1)
+        .with_register(VOUT_MODE, &[0x40])
+        .with_coefficients(VOUT_COMMAND, false, Coefficients::new(2, 0, 2))
+        .with_coefficients(VOUT_COMMAND, true, Coefficients::new(2, 0, 2));
+    let mut bus = SimulatedBus::new().with_device(ADDRESS, device);
+    block_on(bus.send_vout_command(ADDRESS, Volts(1.5))).unwrap();
+    assert_eq!(
+        bus.device(ADDRESS).unwrap().register(0, VOUT_COMMAND),
+        Some(&300u16.to_le_bytes()[..])
+    );
+    assert_eq!(
+        block_on(bus.read_vout_command(ADDRESS)).unwrap(),
+        Volts(1.5)
+    );
+}
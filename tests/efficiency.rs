@@ -0,0 +1,68 @@
+mod common;
+
+use embedded_hal_mock::eh1::i2c::Transaction;
+use pmbus::commands::{PmBus, MFR_EFFICIENCY_LL};
+use pmbus::efficiency::{EfficiencyData, EfficiencyPoint};
+use pmbus::format::Linear11;
+use pmbus::units::{Percent, Volts, Watts};
+use pollster::block_on;
+
+use self::common::Bus;
+
+const ADDRESS: u8 = 0x40;
+
+fn point(output_power: f64, efficiency: f64) -> EfficiencyPoint {
+    EfficiencyPoint {
+        output_power: Watts(output_power),
+        efficiency: Percent(efficiency),
+    }
+}
+
+fn data() -> EfficiencyData {
+    EfficiencyData {
+        input_voltage: Volts(115.0),
+        points: [point(100.0, 88.0), point(500.0, 94.0), point(1000.0, 92.0)],
+    }
+}
+
+#[test]
+fn read_efficiency() {
+    let mut response = vec![14];
+    for value in [115.0, 100.0, 88.0, 500.0, 94.0, 1000.0, 92.0] {
+        response.extend(Linear11::from_f64(value).unwrap().0.to_le_bytes());
+    }
+    let mut bus = Bus::new(&[
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![MFR_EFFICIENCY_LL]),
+        Transaction::read(ADDRESS, response),
+        Transaction::transaction_end(ADDRESS),
+    ]);
+    assert_eq!(
+        block_on(bus.read_mfr_efficiency_ll(ADDRESS)).unwrap(),
+        data()
+    );
+    bus.done();
+}
+
+#[test]
+fn interpolate() {
+    let data = data();
+    assert_eq!(data.efficiency_at(Watts(100.0)), Percent(88.0));
+    assert_eq!(data.efficiency_at(Watts(300.0)), Percent(91.0));
+    assert_eq!(data.efficiency_at(Watts(750.0)), Percent(93.0));
+    assert_eq!(data.efficiency_at(Watts(1000.0)), Percent(92.0));
+    assert_eq!(
+        data.input_power_at(Watts(500.0)),
+        Some(Watts(50000.0 / 94.0))
+    );
+}
+
+#[test]
+fn clamp_outside_points() {
+    let data = data();
+    assert_eq!(data.efficiency_at(Watts(10.0)), Percent(88.0));
+    assert_eq!(data.efficiency_at(Watts(2000.0)), Percent(92.0));
+    let mut unordered = data;
+    unordered.points.reverse();
+    assert_eq!(unordered.efficiency_at(Watts(300.0)), Percent(91.0));
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use embedded_hal_mock::eh1::i2c::Transaction;
+use pmbus::commands::{PmBus, READ_EIN};
+use pmbus::energy::{EnergyCount, EnergyMeter};
+use pmbus::error::FormatError;
+use pmbus::format::Coefficients;
+use pmbus::units::Watts;
+use pollster::block_on;
+
+use self::common::Bus;
+
+const ADDRESS: u8 = 0x40;
+
+fn count(accumulator: u16, rollover_count: u8, sample_count: u32) -> EnergyCount {
+    EnergyCount {
+        accumulator,
+        rollover_count,
+        sample_count,
+    }
+}
+
+/// `X = Y`, so that raw values are watts.
+const UNIT: Coefficients = Coefficients::new(1, 0, 0);
+
+#[test]
+fn read_ein() {
+    let mut bus = Bus::new(&[
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![READ_EIN]),
+        Transaction::read(ADDRESS, vec![6, 0x64, 0x00, 0x02, 0x10, 0x00, 0x01]),
+        Transaction::transaction_end(ADDRESS),
+    ]);
+    let count = block_on(bus.read_read_ein(ADDRESS)).unwrap();
+    assert_eq!(
+        count,
+        EnergyCount {
+            accumulator: 100,
+            rollover_count: 2,
+            sample_count: 0x010010,
+        }
+    );
+    assert_eq!(<[u8; 6]>::from(count), [0x64, 0x00, 0x02, 0x10, 0x00, 0x01]);
+    assert_eq!(count.total(), 2 * 0x8000 + 100);
+    bus.done();
+}
+
+#[test]
+fn average_power() {
+    let average = EnergyMeter::average_raw(count(100, 0, 10), count(600, 0, 20));
+    assert_eq!(average, Some(50.0));
+}
+
+#[test]
+fn accumulator_rollover() {
+    let average = EnergyMeter::average_raw(count(0x7F00, 0, 10), count(0x0100, 1, 20));
+    assert_eq!(average, Some(51.2));
+}
+
+#[test]
+fn rollover_count_wraps() {
+    let average = EnergyMeter::average_raw(count(0x7F00, 255, 10), count(0x0100, 0, 20));
+    assert_eq!(average, Some(51.2));
+}
+
+#[test]
+fn sample_count_wraps() {
+    let average = EnergyMeter::average_raw(count(100, 0, 0xFF_FFF0), count(320, 0, 0x00_0006));
+    assert_eq!(average, Some(10.0));
+}
+
+#[test]
+fn meter() {
+    let mut meter = EnergyMeter::new(UNIT).unwrap();
+    assert_eq!(meter.sample(count(0x7F00, 0, 10)), None);
+    assert_eq!(meter.sample(count(0x0100, 1, 20)), Some(Watts(51.2)));
+    // No samples were taken by the device.
+    assert_eq!(meter.sample(count(0x0100, 1, 20)), None);
+    assert_eq!(meter.last(), Some(count(0x0100, 1, 20)));
+}
+
+#[test]
+fn meter_decodes_with_coefficients() {
+    // `X = (Y·10^-1 - 2) / 4`, across an accumulator rollover.
+    let mut meter = EnergyMeter::new(Coefficients::new(4, 2, 1)).unwrap();
+    assert_eq!(meter.sample(count(0x7FF0, 0, 1000)), None);
+    // 0x8000 - 0x7FF0 + 0x0F90 = 4000 over 10 samples, so `Y = 400` and `X = (40 - 2) / 4`.
+    assert_eq!(meter.sample(count(0x0F90, 1, 1010)), Some(Watts(9.5)));
+    assert_eq!(
+        EnergyMeter::new(Coefficients::new(0, 0, 0)),
+        Err(FormatError::Invalid)
+    );
+}
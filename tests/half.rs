@@ -0,0 +1,58 @@
+use pmbus::error::FormatError;
+use pmbus::format::Half;
+
+#[test]
+fn decode() {
+    assert_eq!(Half(0x3C00).to_f64(), 1.0);
+    assert_eq!(Half(0xC000).to_f64(), -2.0);
+    assert_eq!(Half(0x3CCD).to_f64(), 1.2001953125);
+    assert_eq!(Half::MAX.to_f64(), 65504.0);
+    assert_eq!(Half::MIN.to_f32(), -65504.0);
+    assert_eq!(Half(0x0001).to_f64(), 2f64.powi(-24));
+    assert_eq!(Half(0x0400).to_f64(), 2f64.powi(-14));
+    assert_eq!(Half::INFINITY.to_f64(), f64::INFINITY);
+    assert_eq!(Half::NEG_INFINITY.to_f64(), f64::NEG_INFINITY);
+    assert!(Half(0x7E00).to_f64().is_nan());
+}
+
+#[test]
+fn encode_rounds_to_nearest_even() {
+    assert_eq!(Half::from_f64(1.2), Ok(Half(0x3CCD)));
+    assert_eq!(Half::from_f32(-0.5), Ok(Half(0xB800)));
+    // Halfway between 1 and the next value up, 1 + 2^-10.
+    assert_eq!(Half::from_f64(1.0 + 2f64.powi(-11)), Ok(Half(0x3C00)));
+    assert_eq!(Half::from_f64(1.0 + 3.0 * 2f64.powi(-11)), Ok(Half(0x3C02)));
+    // Rounding up the mantissa carries into the exponent.
+    assert_eq!(Half::from_f64(2.0 - 2f64.powi(-12)), Ok(Half(0x4000)));
+    // Subnormals, and values rounding up to the smallest normal.
+    assert_eq!(Half::from_f64(1.5 * 2f64.powi(-24)), Ok(Half(0x0002)));
+    assert_eq!(Half::from_f64(2.5 * 2f64.powi(-24)), Ok(Half(0x0002)));
+    assert_eq!(
+        Half::from_f64(2f64.powi(-14) - 2f64.powi(-26)),
+        Ok(Half(0x0400))
+    );
+    assert_eq!(Half::from_f64(2f64.powi(-26)), Ok(Half(0x0000)));
+    assert_eq!(Half::from_f64(-1e-30), Ok(Half(0x8000)));
+}
+
+#[test]
+fn encode_rejects_out_of_range() {
+    assert_eq!(Half::from_f64(65519.0), Ok(Half::MAX));
+    assert_eq!(Half::from_f64(65520.0), Err(FormatError::OutOfRange));
+    assert_eq!(Half::from_f64(-1e6), Err(FormatError::OutOfRange));
+    assert_eq!(Half::from_f64(f64::INFINITY), Err(FormatError::NotFinite));
+    assert_eq!(Half::from_f32(f32::NAN), Err(FormatError::NotFinite));
+}
+
+#[test]
+fn round_trip_every_finite_value() {
+    for raw in 0..=u16::MAX {
+        let value = Half(raw);
+        if (raw >> 10) & 0x1F == 0x1F {
+            assert_eq!(Half::from_f64(value.to_f64()), Err(FormatError::NotFinite));
+        } else {
+            assert_eq!(Half::from_f64(value.to_f64()), Ok(value));
+            assert_eq!(Half::try_from(f32::from(value)), Ok(value));
+        }
+    }
+}
@@ -0,0 +1,161 @@
+mod common;
+
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::i2c::Transaction;
+use pmbus::commands::{
+    PmBusExt, IC_DEVICE_ID, IC_DEVICE_REV, MFR_DATE, MFR_ID, MFR_LOCATION, MFR_MODEL, MFR_REVISION,
+    MFR_SERIAL,
+};
+use pmbus::error::PmBusError;
+use pmbus::identity::{decode_text, DeviceIdentity, MfrDate};
+use pmbus::sim::{SimulatedBus, SimulatedDevice};
+use pollster::block_on;
+
+use self::common::{padded, Bus};
+
+const ADDRESS: u8 = 0x40;
+
+fn block_read(command: u8, text: &[u8]) -> Vec<Transaction> {
+    let mut response = vec![text.len() as u8];
+    response.extend_from_slice(text);
+    vec![
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![command]),
+        Transaction::read(ADDRESS, padded(&response, false)),
+        Transaction::transaction_end(ADDRESS),
+    ]
+}
+
+fn block_write(command: u8, text: &[u8]) -> Transaction {
+    let mut write = vec![command, text.len() as u8];
+    write.extend_from_slice(text);
+    Transaction::write(ADDRESS, write)
+}
+
+#[test]
+fn decode() {
+    assert_eq!(decode_text(b"  ACME\0\0"), "ACME");
+    assert_eq!(decode_text(&[b'A', 0xC3, b'B', 0xFF, 0xFF]), "A\u{FFFD}B");
+    assert_eq!(decode_text(&[0xFF; 4]), "");
+}
+
+#[test]
+fn parse_date() {
+    let date = MfrDate::parse(b"240229 ").unwrap();
+    assert_eq!((date.year(), date.month(), date.day()), (2024, 2, 29));
+    assert_eq!(date.to_string(), "2024-02-29");
+    assert_eq!(&date.to_bytes(), b"240229");
+    assert_eq!(MfrDate::parse(b"230229"), None);
+    assert_eq!(MfrDate::parse(b"241301"), None);
+    assert_eq!(MfrDate::parse(b"2401"), None);
+    assert_eq!(MfrDate::parse(&[0xFF; 6]), None);
+    assert_eq!(MfrDate::new(1999, 12, 31), None);
+}
+
+#[test]
+fn read_identity() {
+    let expectations = [
+        block_read(MFR_ID, b"ACME    "),
+        block_read(MFR_MODEL, b"PSU-2000"),
+        block_read(MFR_REVISION, b"A1"),
+        block_read(MFR_LOCATION, b"XYZ\0"),
+        block_read(MFR_DATE, b"240115"),
+        block_read(MFR_SERIAL, b"SN0001"),
+        block_read(IC_DEVICE_ID, b"CTRL"),
+        block_read(IC_DEVICE_REV, b"\xFF\xFF"),
+    ]
+    .concat();
+    let mut bus = Bus::new(&expectations);
+    let identity = block_on(bus.read_identity(ADDRESS)).unwrap();
+    assert_eq!(
+        identity,
+        DeviceIdentity {
+            manufacturer: "ACME".into(),
+            model: "PSU-2000".into(),
+            revision: "A1".into(),
+            location: Some("XYZ".into()),
+            date: MfrDate::new(2024, 1, 15),
+            serial: "SN0001".into(),
+            ic_device_id: Some("CTRL".into()),
+            ic_device_revision: Some("".into()),
+        }
+    );
+    bus.done();
+}
+
+#[test]
+fn read_identity_optional() {
+    let device = SimulatedDevice::new(1)
+        .with_register(MFR_ID, b"ACME")
+        .with_register(MFR_MODEL, b"PSU-2000")
+        .with_register(MFR_REVISION, b"A1")
+        .with_register(MFR_SERIAL, b"SN0001")
+        .without_command(MFR_LOCATION)
+        .without_command(MFR_DATE)
+        .without_command(IC_DEVICE_ID)
+        .without_command(IC_DEVICE_REV);
+    let mut bus = SimulatedBus::new().with_device(ADDRESS, device);
+    assert_eq!(
+        block_on(bus.read_identity(ADDRESS)).unwrap(),
+        DeviceIdentity {
+            manufacturer: "ACME".into(),
+            model: "PSU-2000".into(),
+            revision: "A1".into(),
+            serial: "SN0001".into(),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn read_identity_required() {
+    let device = SimulatedDevice::new(1).without_command(MFR_ID);
+    let mut bus = SimulatedBus::new().with_device(ADDRESS, device);
+    assert_eq!(
+        block_on(bus.read_identity(ADDRESS)),
+        Err(PmBusError::Bus(ErrorKind::NoAcknowledge(
+            NoAcknowledgeSource::Data
+        )))
+    );
+}
+
+#[test]
+fn unsupported_errors() {
+    let nack = |source| PmBusError::Bus(ErrorKind::NoAcknowledge(source));
+    assert!(nack(NoAcknowledgeSource::Data).is_unsupported());
+    assert!(PmBusError::<ErrorKind>::Unsupported { command: MFR_ID }.is_unsupported());
+    // A device which went missing must not be mistaken for one without the command.
+    assert!(!nack(NoAcknowledgeSource::Address).is_unsupported());
+    assert!(!nack(NoAcknowledgeSource::Unknown).is_unsupported());
+}
+
+#[test]
+fn provision_identity() {
+    let mut bus = Bus::new(&[
+        block_write(MFR_ID, b"ACME"),
+        block_write(MFR_DATE, b"240115"),
+        block_write(MFR_SERIAL, b"SN0001"),
+    ]);
+    let identity = DeviceIdentity {
+        manufacturer: "ACME".into(),
+        date: MfrDate::new(2024, 1, 15),
+        serial: "SN0001".into(),
+        ic_device_id: Some("ignored".into()),
+        ..Default::default()
+    };
+    block_on(bus.provision_identity(ADDRESS, &identity)).unwrap();
+    bus.done();
+}
+
+#[test]
+fn provision_too_long() {
+    let mut bus = Bus::new(&[]);
+    let identity = DeviceIdentity {
+        manufacturer: "ACME".into(),
+        serial: "0".repeat(40),
+        ..Default::default()
+    };
+    let error = block_on(bus.provision_identity(ADDRESS, &identity)).unwrap_err();
+    assert_eq!(error, PmBusError::BlockTooLong { len: 40, max: 32 });
+    bus.done();
+}
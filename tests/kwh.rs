@@ -0,0 +1,77 @@
+mod common;
+
+use embedded_hal_mock::eh1::i2c::Transaction;
+use pmbus::commands::{PmBus, READ_KWH_CONFIG, READ_KWH_IN};
+use pmbus::energy::{KwhConfig, KwhCounter, KwhDirection, KwhTotal};
+use pmbus::units::Joules;
+use pollster::block_on;
+
+use self::common::Bus;
+
+const ADDRESS: u8 = 0x40;
+
+#[test]
+fn read_kwh_in() {
+    let mut bus = Bus::new(&[Transaction::write_read(
+        ADDRESS,
+        vec![READ_KWH_IN],
+        vec![0x78, 0x56, 0x34, 0x12],
+    )]);
+    let counter = block_on(bus.read_read_kwh_in(ADDRESS)).unwrap();
+    assert_eq!(counter, KwhCounter(0x12345678));
+    assert_eq!(KwhCounter(2).to_joules(), Joules(7.2e6));
+    bus.done();
+}
+
+#[test]
+fn kwh_config() {
+    let mut bus = Bus::new(&[
+        Transaction::write_read(ADDRESS, vec![READ_KWH_CONFIG], vec![0x01, 0x81]),
+        Transaction::write(ADDRESS, vec![READ_KWH_CONFIG, 0x01, 0x83]),
+    ]);
+    let config = block_on(bus.read_read_kwh_config(ADDRESS)).unwrap();
+    assert!(config.enabled(KwhDirection::In));
+    assert!(config.enabled(KwhDirection::Out));
+    assert!(!config.reset(KwhDirection::In));
+    assert!(!config.reset(KwhDirection::Out));
+    assert_eq!(config.reserved_bits(), 0x8000);
+    block_on(bus.send_read_kwh_config(ADDRESS, config.with_reset(KwhDirection::Out, true)))
+        .unwrap();
+    bus.done();
+}
+
+#[test]
+fn kwh_config_bits() {
+    let config = KwhConfig::default()
+        .with_enabled(KwhDirection::In, true)
+        .with_reset(KwhDirection::In, true)
+        .with_enabled(KwhDirection::Out, true)
+        .with_reset(KwhDirection::Out, true);
+    assert_eq!(u16::from(config), KwhConfig::DEFINED);
+    assert_eq!(KwhConfig::DEFINED, 0x0303);
+    assert_eq!(
+        KwhConfig(0xFFFF)
+            .with_enabled(KwhDirection::In, false)
+            .with_reset(KwhDirection::Out, false),
+        KwhConfig(0xFDFE)
+    );
+    assert_eq!(KwhConfig(0xFFFF).reserved_bits(), 0xFCFC);
+}
+
+#[test]
+fn total_across_wraparound() {
+    let mut total = KwhTotal::new();
+    assert_eq!(total.total(), None);
+    assert_eq!(total.update(KwhCounter(u32::MAX - 1)), 0xFFFF_FFFE);
+    assert_eq!(total.update(KwhCounter(u32::MAX)), 0xFFFF_FFFF);
+    assert_eq!(total.update(KwhCounter(3)), 0x1_0000_0003);
+    assert_eq!(total.update(KwhCounter(3)), 0x1_0000_0003);
+    assert_eq!(total.total(), Some(0x1_0000_0003));
+}
+
+#[test]
+fn resume_total() {
+    let mut total = KwhTotal::resume(0x2_0000_0010);
+    assert_eq!(total.update(KwhCounter(0x20)), 0x2_0000_0020);
+    assert_eq!(total.update(KwhCounter(0x05)), 0x3_0000_0005);
+}
@@ -0,0 +1,115 @@
+use pmbus::error::FormatError;
+use pmbus::format::Linear11;
+
+/// Deterministic xorshift sequence, so that sweeps are reproducible.
+fn xorshift(mut state: u64) -> impl Iterator<Item = u64> {
+    std::iter::repeat_with(move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    })
+}
+
+#[test]
+fn fields_are_sign_extended() {
+    let value = Linear11(0b11101_11111111111);
+    assert_eq!(value.exponent(), -3);
+    assert_eq!(value.mantissa(), -1);
+    assert_eq!(value.to_f64(), -0.125);
+
+    let value = Linear11(0b01111_01111111111);
+    assert_eq!(value.exponent(), 15);
+    assert_eq!(value.mantissa(), 1023);
+    assert_eq!(value.to_f64(), Linear11::MAX);
+
+    let value = Linear11(0b01111_10000000000);
+    assert_eq!(value.mantissa(), -1024);
+    assert_eq!(value.to_f64(), Linear11::MIN);
+
+    let value = Linear11(0b10000_00000000001);
+    assert_eq!(value.exponent(), -16);
+    assert_eq!(value.to_f32(), 2f32.powi(-16));
+}
+
+#[test]
+fn new_rejects_out_of_range_fields() {
+    assert_eq!(Linear11::new(-3, -1), Some(Linear11(0b11101_11111111111)));
+    assert_eq!(Linear11::new(16, 0), None);
+    assert_eq!(Linear11::new(-17, 0), None);
+    assert_eq!(Linear11::new(0, 1024), None);
+    assert_eq!(Linear11::new(0, -1025), None);
+}
+
+#[test]
+fn encode_picks_smallest_exponent() {
+    // 12 V is 768·2^-6, but 12·2^-16 would overflow the mantissa.
+    let value = Linear11::from_f64(12.0).unwrap();
+    assert_eq!((value.exponent(), value.mantissa()), (-6, 768));
+    let value = Linear11::from_f32(-0.5).unwrap();
+    assert_eq!((value.exponent(), value.mantissa()), (-11, -1024));
+}
+
+#[test]
+fn encode_rounds_to_nearest() {
+    // 1/3 with exponent -11 is 682.67·2^-11.
+    let value = Linear11::from_f64(1.0 / 3.0).unwrap();
+    assert_eq!((value.exponent(), value.mantissa()), (-11, 683));
+}
+
+#[test]
+fn encode_errors() {
+    assert_eq!(
+        Linear11::from_f64(Linear11::MAX + 2f64.powi(14)),
+        Err(FormatError::OutOfRange)
+    );
+    assert_eq!(
+        Linear11::from_f64(Linear11::MIN - 2f64.powi(15)),
+        Err(FormatError::OutOfRange)
+    );
+    assert_eq!(Linear11::from_f64(f64::NAN), Err(FormatError::NotFinite));
+    assert_eq!(
+        Linear11::try_from(f32::INFINITY),
+        Err(FormatError::NotFinite)
+    );
+    assert!(Linear11::from_f64(Linear11::MAX).is_ok());
+    assert!(Linear11::from_f64(Linear11::MIN).is_ok());
+}
+
+#[test]
+fn round_trip_every_word() {
+    for raw in 0..=u16::MAX {
+        let value = Linear11(raw).to_f64();
+        assert_eq!(Linear11(raw).to_f32() as f64, value, "{raw:#06X}");
+        let encoded = Linear11::from_f64(value).unwrap();
+        assert_eq!(encoded.to_f64(), value, "{raw:#06X}");
+        // The encoding is normalized, so that no precision is wasted.
+        assert!(
+            encoded.exponent() == -16 || encoded.mantissa().unsigned_abs() >= 512,
+            "{raw:#06X} encoded as {encoded:?}"
+        );
+    }
+}
+
+#[test]
+fn round_trip_within_half_step() {
+    for bits in xorshift(0x9E37_79B9_7F4A_7C15).take(100_000) {
+        // Spread magnitudes across the whole range of exponents.
+        let fraction = (bits >> 11) as f64 / (1u64 << 53) as f64;
+        let scale = 2f64.powi((bits % 48) as i32 - 20);
+        let value = (fraction * 2.0 - 1.0) * scale;
+        match Linear11::from_f64(value) {
+            Ok(encoded) => {
+                let step = 2f64.powi(encoded.exponent() as i32);
+                assert!(
+                    (encoded.to_f64() - value).abs() <= step / 2.0,
+                    "{value} encoded as {encoded:?}"
+                );
+            }
+            Err(error) => {
+                assert_eq!(error, FormatError::OutOfRange);
+                assert!(!(Linear11::MIN..=Linear11::MAX).contains(&value), "{value}");
+            }
+        }
+    }
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use embedded_hal_mock::eh1::i2c::Transaction;
+use pmbus::commands::{PmBus, ON_OFF_CONFIG};
+use pmbus::error::{FormatError, PmBusError};
+use pmbus::on_off_config::{ControlPolarity, OnOffConfig, PowerupBehaviour, TurnOffAction};
+use pollster::block_on;
+
+use self::common::Bus;
+
+const ADDRESS: u8 = 0x40;
+
+#[test]
+fn encode() {
+    assert_eq!(u8::try_from(OnOffConfig::always_on()), Ok(0x00));
+    assert_eq!(u8::try_from(OnOffConfig::by_command()), Ok(0x18));
+    assert_eq!(
+        u8::try_from(OnOffConfig::by_control_pin(ControlPolarity::ActiveHigh)),
+        Ok(0x16)
+    );
+    assert_eq!(
+        u8::try_from(
+            OnOffConfig::by_command_and_control_pin(ControlPolarity::ActiveLow)
+                .with_turn_off_action(TurnOffAction::Immediate)
+        ),
+        Ok(0x1D)
+    );
+}
+
+#[test]
+fn decode() {
+    assert_eq!(
+        OnOffConfig::try_from(0x17),
+        Ok(OnOffConfig {
+            powerup: PowerupBehaviour::Commanded,
+            command_enabled: false,
+            control_pin_enabled: true,
+            polarity: ControlPolarity::ActiveHigh,
+            turn_off_action: TurnOffAction::Immediate,
+        })
+    );
+    assert_eq!(OnOffConfig::try_from(0x20), Err(FormatError::Reserved));
+    // Impossible to turn on, but still read as it is.
+    assert_eq!(
+        OnOffConfig::try_from(0x10).unwrap().validate(),
+        Err(FormatError::Invalid)
+    );
+}
+
+#[test]
+fn refuse_impossible_to_turn_on() {
+    let mut bus = Bus::new(&[
+        Transaction::write_read(ADDRESS, vec![ON_OFF_CONFIG], vec![0x1E]),
+        Transaction::write(ADDRESS, vec![ON_OFF_CONFIG, 0x16]),
+    ]);
+    let config = block_on(bus.read_on_off_config(ADDRESS)).unwrap();
+    assert!(config.command_enabled && config.control_pin_enabled);
+    let without_command = OnOffConfig {
+        command_enabled: false,
+        ..config
+    };
+    block_on(bus.send_on_off_config(ADDRESS, without_command)).unwrap();
+    let without_either = OnOffConfig {
+        control_pin_enabled: false,
+        ..without_command
+    };
+    assert_eq!(
+        block_on(bus.send_on_off_config(ADDRESS, without_either)),
+        Err(PmBusError::Format(FormatError::Invalid))
+    );
+    bus.done();
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use embedded_hal_mock::eh1::i2c::Transaction;
+use pmbus::commands::{PmBusExt, OPERATION};
+use pmbus::error::{FormatError, PmBusError};
+use pmbus::operation::{Margin, MarginFaultResponse, Operation, VoltageSource};
+use pollster::block_on;
+
+use self::common::Bus;
+
+const ADDRESS: u8 = 0x40;
+
+#[test]
+fn constructors() {
+    assert_eq!(u8::from(Operation::on()), 0x80);
+    assert_eq!(u8::from(Operation::off_immediate()), 0x00);
+    assert_eq!(u8::from(Operation::soft_off()), 0x40);
+    assert_eq!(u8::from(Operation::margin_low(true)), 0x94);
+    assert_eq!(u8::from(Operation::margin_low(false)), 0x98);
+    assert_eq!(u8::from(Operation::margin_high(true)), 0xA4);
+    assert_eq!(u8::from(Operation::margin_high(false)), 0xA8);
+}
+
+#[test]
+fn parse() {
+    assert_eq!(
+        Operation::try_from(0xBA),
+        Ok(Operation {
+            on: true,
+            soft_off: false,
+            source: VoltageSource::AvsBus,
+            margin_fault_response: Some(MarginFaultResponse::Act),
+            transition_control: true,
+        })
+    );
+    assert_eq!(
+        Operation::try_from(0xA4).unwrap().margin(),
+        Some(Margin::High {
+            ignore_faults: true
+        })
+    );
+    for raw in 0..=u8::MAX {
+        if let Ok(operation) = Operation::try_from(raw) {
+            assert_eq!(Operation::try_from(u8::from(operation)), Ok(operation));
+        }
+    }
+}
+
+#[test]
+fn dont_care_bits() {
+    // Off, with junk in bits 5:0.
+    assert_eq!(Operation::try_from(0x0C), Ok(Operation::off_immediate()));
+    assert_eq!(Operation::try_from(0x4D), Ok(Operation::soft_off()));
+    // Off, keeping a valid margin setting for when turned on.
+    assert_eq!(
+        Operation::try_from(0x14),
+        Ok(Operation {
+            on: false,
+            ..Operation::margin_low(true)
+        })
+    );
+    // Off, with margining but no margin fault response.
+    assert_eq!(Operation::try_from(0x10), Ok(Operation::off_immediate()));
+    // Nominal, with junk in the margin fault response.
+    assert_eq!(Operation::try_from(0x8C), Ok(Operation::on()));
+    assert_eq!(Operation::try_from(0x84), Ok(Operation::on()));
+    // Soft off is not written while on.
+    assert_eq!(
+        u8::from(Operation {
+            on: true,
+            ..Operation::soft_off()
+        }),
+        0x80
+    );
+}
+
+#[test]
+fn reject_reserved() {
+    // Reserved bit.
+    assert_eq!(Operation::try_from(0x81), Err(FormatError::Reserved));
+    // On and off at once.
+    assert_eq!(Operation::try_from(0xC0), Err(FormatError::Reserved));
+    // Reserved margin fault response.
+    assert_eq!(Operation::try_from(0x9C), Err(FormatError::Reserved));
+    // Margining without a margin fault response.
+    assert_eq!(Operation::try_from(0x90), Err(FormatError::Reserved));
+    assert_eq!(Operation::try_from(0xA0), Err(FormatError::Reserved));
+}
+
+#[test]
+fn read_modify_write() {
+    let mut bus = Bus::new(&[
+        Transaction::write_read(ADDRESS, vec![OPERATION], vec![0x46]),
+        Transaction::write(ADDRESS, vec![OPERATION, 0x82]),
+        Transaction::write_read(ADDRESS, vec![OPERATION], vec![0x82]),
+        Transaction::write(ADDRESS, vec![OPERATION, 0x96]),
+        Transaction::write_read(ADDRESS, vec![OPERATION], vec![0x96]),
+        Transaction::write(ADDRESS, vec![OPERATION, 0x16]),
+        Transaction::write_read(ADDRESS, vec![OPERATION], vec![0xC0]),
+    ]);
+    block_on(bus.turn_on(ADDRESS)).unwrap();
+    block_on(bus.set_margin(
+        ADDRESS,
+        Margin::Low {
+            ignore_faults: true,
+        },
+    ))
+    .unwrap();
+    block_on(bus.turn_off(ADDRESS)).unwrap();
+    assert_eq!(
+        block_on(bus.turn_on(ADDRESS)),
+        Err(PmBusError::Format(FormatError::Reserved))
+    );
+    bus.done();
+}
@@ -0,0 +1,223 @@
+use embedded_hal::i2c::{ErrorKind, I2c, NoAcknowledgeSource};
+use pmbus::commands::{
+    PmBus, PAGE, READ_VIN, STATUS_CML, STATUS_WORD, VOUT_COMMAND, VOUT_MODE, VOUT_TRIM,
+};
+use pmbus::error::{PmBusError, SmBusError};
+use pmbus::format::{Linear11, VoutMode};
+use pmbus::sim::{SimulatedBus, SimulatedDevice};
+use pmbus::smbus::{Pec, SmBus};
+use pmbus::status::{StatusByte, StatusCml, StatusWord};
+use pmbus::units::Volts;
+use pollster::block_on;
+
+const ADDRESS: u8 = 0x40;
+
+const DATA_NACK: ErrorKind = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data);
+
+fn bus() -> SimulatedBus {
+    SimulatedBus::new().with_device(ADDRESS, SimulatedDevice::new(2))
+}
+
+#[test]
+fn registers_are_paged() {
+    let mut bus = bus();
+    block_on(bus.send_page(ADDRESS, 1)).unwrap();
+    block_on(bus.send_vout_trim(ADDRESS, 0x1234)).unwrap();
+    assert_eq!(block_on(bus.read_vout_trim(ADDRESS)).unwrap(), 0x1234);
+    block_on(bus.send_page(ADDRESS, 0)).unwrap();
+    assert_eq!(block_on(bus.read_vout_trim(ADDRESS)).unwrap(), 0x0000);
+    let device = bus.device(ADDRESS).unwrap();
+    assert_eq!(device.register(1, VOUT_TRIM), Some(&[0x34, 0x12][..]));
+}
+
+#[test]
+fn page_all_writes_every_page() {
+    let mut bus = bus();
+    block_on(bus.send_page(ADDRESS, 0xFF)).unwrap();
+    block_on(bus.send_vout_mode(
+        ADDRESS,
+        VoutMode::Linear {
+            exponent: -9,
+            relative: false,
+        },
+    ))
+    .unwrap();
+    let device = bus.device(ADDRESS).unwrap();
+    assert_eq!(device.register(0, VOUT_MODE), Some(&[0x17][..]));
+    assert_eq!(device.register(1, VOUT_MODE), Some(&[0x17][..]));
+}
+
+#[test]
+fn invalid_page() {
+    let mut bus = bus();
+    let error = block_on(bus.send_page(ADDRESS, 2)).unwrap_err();
+    assert_eq!(error, PmBusError::Bus(DATA_NACK));
+    assert_eq!(block_on(bus.read_page(ADDRESS)).unwrap(), 0);
+    assert_eq!(
+        block_on(bus.read_status_cml(ADDRESS)).unwrap(),
+        StatusCml::INVALID_DATA
+    );
+}
+
+#[test]
+fn read_only_command_sets_cml() {
+    let mut bus = bus();
+    let error = block_on(bus.write_word(ADDRESS, READ_VIN, 0x1234)).unwrap_err();
+    assert_eq!(error, SmBusError::Bus(DATA_NACK));
+    assert_eq!(
+        block_on(bus.read_status_cml(ADDRESS)).unwrap(),
+        StatusCml::INVALID_COMMAND
+    );
+    assert_eq!(
+        block_on(bus.read_status_word(ADDRESS)).unwrap(),
+        StatusWord::CML
+    );
+    assert_eq!(
+        block_on(bus.read_status_byte(ADDRESS)).unwrap(),
+        StatusByte::CML
+    );
+    assert_eq!(
+        bus.device(ADDRESS).unwrap().register(0, READ_VIN),
+        Some(&[0x00, 0x00][..])
+    );
+}
+
+#[test]
+fn refused_command_extends_short_status_registers() {
+    let mut bus = bus();
+    let device = bus.device_mut(ADDRESS).unwrap();
+    device.set_register(0, STATUS_CML, &[]);
+    device.set_register(0, STATUS_WORD, &[0x00]);
+    assert!(block_on(bus.write_word(ADDRESS, READ_VIN, 0x1234)).is_err());
+    let device = bus.device(ADDRESS).unwrap();
+    assert_eq!(
+        device.register(0, STATUS_CML),
+        Some(&[StatusCml::INVALID_COMMAND.bits()][..])
+    );
+    assert_eq!(device.register(0, STATUS_WORD), Some(&[0x02, 0x00][..]));
+}
+
+#[test]
+fn status_byte_of_empty_status_word() {
+    let device = SimulatedDevice::new(1).with_register(STATUS_WORD, &[]);
+    let mut bus = SimulatedBus::new().with_device(ADDRESS, device);
+    assert_eq!(
+        block_on(bus.read_status_byte(ADDRESS)).unwrap(),
+        StatusByte::empty()
+    );
+}
+
+#[test]
+fn reserved_and_removed_commands_are_invalid() {
+    let mut bus = SimulatedBus::new().with_device(
+        ADDRESS,
+        SimulatedDevice::new(1).without_command(VOUT_COMMAND),
+    );
+    assert!(block_on(bus.read_byte(ADDRESS, 0x09)).is_err());
+    assert!(block_on(bus.read_vout_command(ADDRESS)).is_err());
+    assert_eq!(
+        block_on(bus.read_status_cml(ADDRESS)).unwrap(),
+        StatusCml::INVALID_COMMAND
+    );
+}
+
+#[test]
+fn clear_faults() {
+    let mut bus = bus();
+    bus.device_mut(ADDRESS)
+        .unwrap()
+        .set_register(0, STATUS_CML, &[StatusCml::INVALID_DATA.bits()]);
+    block_on(bus.write_clear_faults(ADDRESS)).unwrap();
+    assert_eq!(
+        block_on(bus.read_status_cml(ADDRESS)).unwrap(),
+        StatusCml::empty()
+    );
+}
+
+#[test]
+fn status_write_clears_bits() {
+    let mut bus = bus();
+    bus.device_mut(ADDRESS).unwrap().set_register(
+        0,
+        STATUS_CML,
+        &[(StatusCml::INVALID_COMMAND | StatusCml::INVALID_DATA).bits()],
+    );
+    block_on(bus.send_status_cml(ADDRESS, StatusCml::INVALID_DATA)).unwrap();
+    assert_eq!(
+        block_on(bus.read_status_cml(ADDRESS)).unwrap(),
+        StatusCml::INVALID_COMMAND
+    );
+}
+
+#[test]
+fn query_and_capability() {
+    let mut bus = SimulatedBus::new().with_device(
+        ADDRESS,
+        SimulatedDevice::new(1)
+            .with_capability(0x20)
+            .with_query(READ_VIN, 0b1010_0000),
+    );
+    assert_eq!(block_on(bus.read_capability(ADDRESS)).unwrap(), 0x20);
+    assert_eq!(
+        block_on(bus.call_query(ADDRESS, &[READ_VIN])).unwrap(),
+        [0b1010_0000]
+    );
+    assert_eq!(
+        block_on(bus.call_query(ADDRESS, &[VOUT_COMMAND])).unwrap(),
+        [0b1111_1100]
+    );
+    assert_eq!(block_on(bus.call_query(ADDRESS, &[0x09])).unwrap(), [0x00]);
+}
+
+#[test]
+fn pec_round_trip() {
+    let mut bus = bus().with_pec();
+    bus.device_mut(ADDRESS)
+        .unwrap()
+        .set_register(0, READ_VIN, &[0xCD, 0xAB]);
+    assert_eq!(
+        block_on(bus.read_read_vin(ADDRESS)).unwrap(),
+        Volts(Linear11(0xABCD).to_f64())
+    );
+    block_on(bus.send_mfr_id(ADDRESS, b"ACME")).unwrap();
+    assert_eq!(block_on(bus.read_mfr_id(ADDRESS)).unwrap(), b"ACME");
+}
+
+#[test]
+fn pec_failure_sets_cml() {
+    let mut bus = bus();
+    let pec = Pec::new()
+        .address(ADDRESS, false)
+        .update(&[VOUT_COMMAND, 0x34, 0x12])
+        .value();
+    let error = I2c::write(&mut bus, ADDRESS, &[VOUT_COMMAND, 0x34, 0x12, !pec]).unwrap_err();
+    assert_eq!(error, DATA_NACK);
+    let device = bus.device(ADDRESS).unwrap();
+    assert_eq!(device.register(0, VOUT_COMMAND), Some(&[0x00, 0x00][..]));
+    assert_eq!(
+        device.register(0, STATUS_CML),
+        Some(&[StatusCml::PEC_FAILED.bits()][..])
+    );
+    assert_eq!(device.register(0, STATUS_WORD), Some(&[1 << 1, 0x00][..]));
+}
+
+#[test]
+fn no_device() {
+    let mut bus = bus();
+    let error = block_on(bus.read_page(0x41)).unwrap_err();
+    assert_eq!(
+        error,
+        PmBusError::Bus(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))
+    );
+}
+
+#[test]
+fn blocking() {
+    use pmbus::commands::blocking::PmBus;
+
+    let mut bus = bus();
+    PmBus::send_page(&mut bus, ADDRESS, 1).unwrap();
+    assert_eq!(PmBus::read_page(&mut bus, ADDRESS).unwrap(), 1);
+    assert_eq!(bus.device(ADDRESS).unwrap().page(), 1);
+    assert_eq!(bus.device(ADDRESS).unwrap().register(0, PAGE), None);
+}
@@ -0,0 +1,390 @@
+mod common;
+
+use embedded_hal_mock::eh1::i2c::Transaction;
+use pmbus::commands::{PmBus, QUERY, READ_VIN};
+use pmbus::error::SmBusError;
+use pmbus::revision::{PmBusRevision, Revision};
+use pmbus::smbus::{Pec, SmBus, SmBusVersion, SMBUS_3_MAX_BLOCK_SIZE, SMBUS_MAX_BLOCK_SIZE};
+use pollster::block_on;
+
+use self::common::{padded, Bus};
+
+const ADDRESS: u8 = 0x58;
+const COMMAND: u8 = 0x9A;
+
+#[test]
+fn pec_check_value() {
+    // CRC-8/SMBUS check value.
+    assert_eq!(Pec::new().update(b"123456789").value(), 0xF4);
+}
+
+#[test]
+fn block_read_single_transaction() {
+    let mut bus = Bus::new(&[
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![COMMAND]),
+        Transaction::read(ADDRESS, padded(&[3, b'A', b'B', b'C'], false)),
+        Transaction::transaction_end(ADDRESS),
+    ]);
+    let block = block_on(bus.block_read(ADDRESS, COMMAND)).unwrap();
+    assert_eq!(block, b"ABC");
+    bus.done();
+}
+
+#[test]
+fn block_read_empty() {
+    let mut bus = Bus::new(&[
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![COMMAND]),
+        Transaction::read(ADDRESS, padded(&[0], false)),
+        Transaction::transaction_end(ADDRESS),
+    ]);
+    let block = block_on(bus.block_read(ADDRESS, COMMAND)).unwrap();
+    assert!(block.is_empty());
+    bus.done();
+}
+
+#[test]
+fn block_read_count_too_long() {
+    let mut bus = Bus::new(&[
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![COMMAND]),
+        Transaction::read(ADDRESS, padded(&[0xFF], false)),
+        Transaction::transaction_end(ADDRESS),
+    ]);
+    let error = block_on(bus.block_read(ADDRESS, COMMAND)).unwrap_err();
+    assert_eq!(
+        error,
+        SmBusError::BlockTooLong {
+            len: 0xFF,
+            max: SMBUS_MAX_BLOCK_SIZE
+        }
+    );
+    bus.done();
+}
+
+#[test]
+fn block_read_exact_reads_count_bytes() {
+    let mut bus = Bus::new(&[
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![COMMAND]),
+        Transaction::read(ADDRESS, vec![4, 1, 2, 3, 4]),
+        Transaction::transaction_end(ADDRESS),
+    ]);
+    let block = block_on(bus.block_read_exact(ADDRESS, COMMAND, 4)).unwrap();
+    assert_eq!(block, [1, 2, 3, 4]);
+    bus.done();
+}
+
+#[test]
+fn block_read_exact_count_mismatch() {
+    let mut bus = Bus::new(&[
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![COMMAND]),
+        Transaction::read(ADDRESS, vec![3, 1, 2, 3, 0xFF]),
+        Transaction::transaction_end(ADDRESS),
+    ]);
+    let error = block_on(bus.block_read_exact(ADDRESS, COMMAND, 4)).unwrap_err();
+    assert_eq!(
+        error,
+        SmBusError::ByteCountMismatch {
+            expected: 4,
+            received: 3
+        }
+    );
+    bus.done();
+}
+
+#[test]
+fn block_read_exact_with_pec() {
+    let pec = Pec::new()
+        .address(ADDRESS, false)
+        .update(&[COMMAND])
+        .address(ADDRESS, true)
+        .update(&[2, 0x12, 0x34])
+        .value();
+    let mut bus = Bus::with_pec(&[
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![COMMAND]),
+        Transaction::read(ADDRESS, vec![2, 0x12, 0x34, pec]),
+        Transaction::transaction_end(ADDRESS),
+    ]);
+    let block = block_on(bus.block_read_exact(ADDRESS, COMMAND, 2)).unwrap();
+    assert_eq!(block, [0x12, 0x34]);
+    bus.done();
+}
+
+#[test]
+fn block_read_pec_mismatch() {
+    let pec = Pec::new()
+        .address(ADDRESS, false)
+        .update(&[COMMAND])
+        .address(ADDRESS, true)
+        .update(&[1, 0x55])
+        .value();
+    let mut bus = Bus::with_pec(&[
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![COMMAND]),
+        Transaction::read(ADDRESS, padded(&[1, 0x55, !pec], true)),
+        Transaction::transaction_end(ADDRESS),
+    ]);
+    let error = block_on(bus.block_read(ADDRESS, COMMAND)).unwrap_err();
+    assert_eq!(
+        error,
+        SmBusError::Pec {
+            expected: pec,
+            received: !pec
+        }
+    );
+    bus.done();
+}
+
+#[test]
+fn block_read_missing_pec() {
+    // The device stops driving SDA after the data, so the PEC byte reads as released bus.
+    let pec = Pec::new()
+        .address(ADDRESS, false)
+        .update(&[COMMAND])
+        .address(ADDRESS, true)
+        .update(&[1, 0x55])
+        .value();
+    let mut bus = Bus::with_pec(&[
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![COMMAND]),
+        Transaction::read(ADDRESS, padded(&[1, 0x55], true)),
+        Transaction::transaction_end(ADDRESS),
+    ]);
+    let error = block_on(bus.block_read(ADDRESS, COMMAND)).unwrap_err();
+    assert_eq!(
+        error,
+        SmBusError::Pec {
+            expected: pec,
+            received: 0xFF
+        }
+    );
+    bus.done();
+}
+
+#[test]
+fn block_write_with_pec() {
+    let pec = Pec::new()
+        .address(ADDRESS, false)
+        .update(&[COMMAND, 2, 0xAB, 0xCD])
+        .value();
+    let mut bus = Bus::with_pec(&[Transaction::write(
+        ADDRESS,
+        vec![COMMAND, 2, 0xAB, 0xCD, pec],
+    )]);
+    block_on(bus.block_write(ADDRESS, COMMAND, &[0xAB, 0xCD])).unwrap();
+    bus.done();
+}
+
+#[test]
+fn block_write_too_long() {
+    let mut bus = Bus::new(&[]);
+    let error =
+        block_on(bus.block_write(ADDRESS, COMMAND, &[0x00; SMBUS_MAX_BLOCK_SIZE + 1])).unwrap_err();
+    assert_eq!(
+        error,
+        SmBusError::BlockTooLong {
+            len: SMBUS_MAX_BLOCK_SIZE + 1,
+            max: SMBUS_MAX_BLOCK_SIZE
+        }
+    );
+    bus.done();
+}
+
+#[test]
+fn block_process_call_single_transaction() {
+    let mut bus = Bus::new(&[
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![COMMAND, 2, 0x01, 0x02]),
+        Transaction::read(ADDRESS, padded(&[3, 0x0A, 0x0B, 0x0C], false)),
+        Transaction::transaction_end(ADDRESS),
+    ]);
+    let block = block_on(bus.block_process_call(ADDRESS, COMMAND, &[0x01, 0x02])).unwrap();
+    assert_eq!(block, [0x0A, 0x0B, 0x0C]);
+    bus.done();
+}
+
+#[test]
+fn block_process_call_with_pec() {
+    let pec = Pec::new()
+        .address(ADDRESS, false)
+        .update(&[COMMAND, 1, 0x01])
+        .address(ADDRESS, true)
+        .update(&[1, 0x0A])
+        .value();
+    let mut bus = Bus::with_pec(&[
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![COMMAND, 1, 0x01]),
+        Transaction::read(ADDRESS, padded(&[1, 0x0A, pec], true)),
+        Transaction::transaction_end(ADDRESS),
+    ]);
+    let block = block_on(bus.block_process_call(ADDRESS, COMMAND, &[0x01])).unwrap();
+    assert_eq!(block, [0x0A]);
+    bus.done();
+}
+
+#[test]
+fn block_process_call_exact_with_pec() {
+    let pec = Pec::new()
+        .address(ADDRESS, false)
+        .update(&[COMMAND, 1, 0x01])
+        .address(ADDRESS, true)
+        .update(&[2, 0x0A, 0x0B])
+        .value();
+    let mut bus = Bus::with_pec(&[
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![COMMAND, 1, 0x01]),
+        Transaction::read(ADDRESS, vec![2, 0x0A, 0x0B, pec]),
+        Transaction::transaction_end(ADDRESS),
+    ]);
+    let block = block_on(bus.block_process_call_exact(ADDRESS, COMMAND, &[0x01], 2)).unwrap();
+    assert_eq!(block, [0x0A, 0x0B]);
+    bus.done();
+}
+
+#[test]
+fn block_process_call_exact_count_mismatch() {
+    let mut bus = Bus::with_pec(&[
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![COMMAND, 1, 0x01]),
+        Transaction::read(ADDRESS, vec![1, 0x0A, 0x5D, 0xFF]),
+        Transaction::transaction_end(ADDRESS),
+    ]);
+    let error = block_on(bus.block_process_call_exact(ADDRESS, COMMAND, &[0x01], 2)).unwrap_err();
+    assert_eq!(
+        error,
+        SmBusError::ByteCountMismatch {
+            expected: 2,
+            received: 1
+        }
+    );
+    bus.done();
+}
+
+#[test]
+fn fixed_size_call_reads_exactly() {
+    let pec = Pec::new()
+        .address(ADDRESS, false)
+        .update(&[QUERY, 1, READ_VIN])
+        .address(ADDRESS, true)
+        .update(&[1, 0xB0])
+        .value();
+    let mut bus = Bus::with_pec(&[
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![QUERY, 1, READ_VIN]),
+        Transaction::read(ADDRESS, vec![1, 0xB0, pec]),
+        Transaction::transaction_end(ADDRESS),
+    ]);
+    let response = block_on(bus.call_query(ADDRESS, &[READ_VIN])).unwrap();
+    assert_eq!(response, [0xB0]);
+    bus.done();
+}
+
+#[test]
+fn smbus_3_block_read() {
+    let mut response = vec![40];
+    response.extend(0..40);
+    response.resize(1 + SMBUS_3_MAX_BLOCK_SIZE, 0xFF);
+    let mut bus = Bus::new(&[
+        Transaction::transaction_start(ADDRESS),
+        Transaction::write(ADDRESS, vec![COMMAND]),
+        Transaction::read(ADDRESS, response),
+        Transaction::transaction_end(ADDRESS),
+    ])
+    .with_max_block_size(SMBUS_3_MAX_BLOCK_SIZE);
+    let block = block_on(bus.block_read(ADDRESS, COMMAND)).unwrap();
+    assert_eq!(block, (0..40).collect::<Vec<u8>>());
+    bus.done();
+}
+
+#[test]
+fn smbus_3_block_write() {
+    let mut write = vec![0xB0, 40];
+    write.extend(0..40);
+    let mut bus =
+        Bus::new(&[Transaction::write(ADDRESS, write)]).with_max_block_size(SMBUS_3_MAX_BLOCK_SIZE);
+    let block = (0..40).collect::<Vec<u8>>();
+    block_on(bus.send_user_data_00(ADDRESS, &block)).unwrap();
+    bus.done();
+}
+
+#[test]
+fn smbus_2_block_write_too_long() {
+    let mut bus = Bus::new(&[]);
+    let error = block_on(bus.send_user_data_00(ADDRESS, &[0x00; 40])).unwrap_err();
+    assert_eq!(
+        error,
+        pmbus::error::PmBusError::BlockTooLong {
+            len: 40,
+            max: SMBUS_MAX_BLOCK_SIZE
+        }
+    );
+    bus.done();
+}
+
+#[test]
+fn revision_negotiation() {
+    let mut bus = Bus::new(&[Transaction::write_read(ADDRESS, vec![0x98], vec![0x33])]);
+    let revision = block_on(bus.read_pmbus_revision(ADDRESS)).unwrap();
+    assert_eq!(
+        revision,
+        PmBusRevision {
+            part_i: Revision::V1_3,
+            part_ii: Revision::V1_3
+        }
+    );
+    assert_eq!(revision.smbus_version(), SmBusVersion::V3);
+    assert_eq!(
+        PmBusRevision::from(0x22).smbus_version().max_block_size(),
+        SMBUS_MAX_BLOCK_SIZE
+    );
+    bus.done();
+}
+
+#[test]
+fn future_revision_uses_smbus_3() {
+    let revision = PmBusRevision::from(0x44);
+    assert_eq!(revision.part_i, Revision::Unknown(4));
+    assert_eq!(revision.smbus_version(), SmBusVersion::V3);
+    assert_eq!(
+        revision.smbus_version().max_block_size(),
+        SMBUS_3_MAX_BLOCK_SIZE
+    );
+}
+
+#[test]
+fn read_32_with_pec() {
+    let pec = Pec::new()
+        .address(ADDRESS, false)
+        .update(&[0x83])
+        .address(ADDRESS, true)
+        .update(&[0x78, 0x56, 0x34, 0x12])
+        .value();
+    let mut bus = Bus::with_pec(&[Transaction::write_read(
+        ADDRESS,
+        vec![0x83],
+        vec![0x78, 0x56, 0x34, 0x12, pec],
+    )]);
+    assert_eq!(
+        block_on(bus.read_read_kwh_in(ADDRESS)).unwrap().kwh(),
+        0x12345678
+    );
+    bus.done();
+}
+
+#[test]
+fn read_write_64() {
+    let data = 0x0123_4567_89AB_CDEF_u64;
+    let mut write = vec![COMMAND];
+    write.extend(data.to_le_bytes());
+    let mut bus = Bus::new(&[
+        Transaction::write(ADDRESS, write),
+        Transaction::write_read(ADDRESS, vec![COMMAND], data.to_le_bytes().to_vec()),
+    ]);
+    block_on(bus.write_64(ADDRESS, COMMAND, data)).unwrap();
+    assert_eq!(block_on(bus.read_64(ADDRESS, COMMAND)).unwrap(), data);
+    bus.done();
+}
@@ -0,0 +1,181 @@
+mod common;
+
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::Transaction;
+use pmbus::commands::{
+    PmBus, PmBusExt, PAGE, STATUS_BYTE, STATUS_CML, STATUS_FANS_1_2, STATUS_FANS_3_4, STATUS_IOUT,
+    STATUS_MFR_SPECIFIC, STATUS_VOUT, STATUS_WORD,
+};
+use pmbus::error::PmBusError;
+use pmbus::sim::{SimulatedBus, SimulatedDevice};
+use pmbus::status::{
+    FaultReport, StatusByte, StatusCml, StatusFans12, StatusFans34, StatusIout, StatusTemperature,
+    StatusVout, StatusWord,
+};
+use pollster::block_on;
+
+use self::common::Bus;
+
+const ADDRESS: u8 = 0x40;
+
+#[test]
+fn read_status() {
+    let mut bus = Bus::new(&[
+        Transaction::write_read(ADDRESS, vec![STATUS_WORD], vec![0x44, 0x88]),
+        Transaction::write_read(ADDRESS, vec![STATUS_BYTE], vec![0x44]),
+        Transaction::write(ADDRESS, vec![STATUS_WORD, 0x00, 0x80]),
+    ]);
+    let status = block_on(bus.read_status_word(ADDRESS)).unwrap();
+    assert_eq!(
+        status,
+        StatusWord::VOUT
+            | StatusWord::POWER_GOOD_NEGATED
+            | StatusWord::OFF
+            | StatusWord::TEMPERATURE
+    );
+    assert!(status.contains(StatusWord::VOUT | StatusWord::OFF));
+    assert!(!status.contains(StatusWord::VOUT | StatusWord::CML));
+    assert!(status.intersects(StatusWord::VOUT | StatusWord::CML));
+    let byte = block_on(bus.read_status_byte(ADDRESS)).unwrap();
+    assert_eq!(byte, status.status_byte());
+    assert_eq!(StatusWord::from(byte), status & StatusWord(0x00FF));
+    block_on(bus.send_status_word(ADDRESS, StatusWord::VOUT)).unwrap();
+    bus.done();
+}
+
+#[test]
+fn display() {
+    let status = StatusWord::IOUT_POUT | StatusWord::POWER_GOOD_NEGATED | StatusWord::CML;
+    assert_eq!(status.to_string(), "IOUT/POUT | POWER_GOOD# | CML");
+    assert_eq!(
+        format!("{status:?}"),
+        "StatusWord(IOUT/POUT | POWER_GOOD# | CML)"
+    );
+    assert_eq!(StatusByte::empty().to_string(), "(empty)");
+    assert_eq!(
+        StatusWord::FLAGS.len(),
+        16,
+        "every bit of STATUS_WORD is named"
+    );
+}
+
+#[test]
+fn read_sub_status() {
+    let mut bus = Bus::new(&[
+        Transaction::write_read(ADDRESS, vec![STATUS_VOUT], vec![0x0C]),
+        Transaction::write_read(ADDRESS, vec![STATUS_FANS_1_2], vec![0x81]),
+    ]);
+    assert_eq!(
+        block_on(bus.read_status_vout(ADDRESS)).unwrap(),
+        StatusVout::VOUT_MAX_MIN_WARNING | StatusVout::TON_MAX_FAULT
+    );
+    let fans = block_on(bus.read_status_fans_1_2(ADDRESS)).unwrap();
+    assert_eq!(
+        fans,
+        StatusFans12::FAN_1_FAULT | StatusFans12::AIRFLOW_WARNING
+    );
+    assert_eq!(fans.to_string(), "FAN_1_FAULT | AIRFLOW_WARNING");
+    bus.done();
+}
+
+#[test]
+fn clear() {
+    let mut bus = Bus::new(&[
+        Transaction::write(ADDRESS, vec![STATUS_CML, 0xA0]),
+        Transaction::write(ADDRESS, vec![STATUS_WORD, 0x02, 0x80]),
+    ]);
+    block_on(bus.clear(ADDRESS, StatusCml::INVALID_COMMAND | StatusCml::PEC_FAILED)).unwrap();
+    block_on(bus.clear(ADDRESS, StatusWord::VOUT | StatusWord::CML)).unwrap();
+    bus.done();
+}
+
+#[test]
+fn display_reserved_bits() {
+    assert_eq!(StatusTemperature(0x81).to_string(), "OT_FAULT | 0x1");
+    assert_eq!(StatusCml(0x04).to_string(), "0x4");
+}
+
+#[test]
+fn fault_reports() {
+    let mut device = SimulatedDevice::new(2);
+    device.set_register(1, STATUS_WORD, &[0x50, 0x14]);
+    device.set_register(1, STATUS_IOUT, &[0x80]);
+    device.set_register(1, STATUS_MFR_SPECIFIC, &[0x02]);
+    device.set_register(1, STATUS_FANS_1_2, &[0x00]);
+    device.set_register(1, STATUS_FANS_3_4, &[0x20]);
+    let mut bus = SimulatedBus::new().with_device(ADDRESS, device);
+    let reports = block_on(bus.read_fault_reports(ADDRESS, &[0, 1], false)).unwrap();
+    assert_eq!(reports[0], FaultReport::new(0, StatusWord::empty()));
+    assert!(reports[0].is_clear());
+    assert_eq!(
+        reports[1],
+        FaultReport {
+            iout: Some(StatusIout::IOUT_OC_FAULT),
+            mfr_specific: Some(0x02),
+            fans_1_2: Some(StatusFans12::empty()),
+            fans_3_4: Some(StatusFans34::FAN_3_WARNING),
+            ..FaultReport::new(
+                1,
+                StatusWord::MFR_SPECIFIC
+                    | StatusWord::FANS
+                    | StatusWord::OFF
+                    | StatusWord::IOUT_OC_FAULT
+            )
+        }
+    );
+    assert_eq!(
+        reports[1].to_string(),
+        "page 1: MFR_SPECIFIC | FANS | OFF | IOUT_OC_FAULT\n  STATUS_IOUT: IOUT_OC_FAULT\n  \
+         STATUS_MFR_SPECIFIC: 0x02\n  STATUS_FANS_1_2: (empty)\n  STATUS_FANS_3_4: FAN_3_WARNING"
+    );
+    assert_eq!(block_on(bus.read_page(ADDRESS)).unwrap(), 0);
+}
+
+#[test]
+fn fault_reports_all_fans() {
+    let mut device = SimulatedDevice::new(1);
+    device.set_register(0, STATUS_WORD, &[0x00, 0x04]);
+    device.set_register(0, STATUS_FANS_1_2, &[0x80]);
+    device.set_register(0, STATUS_FANS_3_4, &[0x20]);
+    let mut bus = SimulatedBus::new().with_device(ADDRESS, device);
+    let reports = block_on(bus.read_fault_reports(ADDRESS, &[0], false)).unwrap();
+    assert_eq!(reports[0].fans_1_2, Some(StatusFans12::FAN_1_FAULT));
+    assert_eq!(reports[0].fans_3_4, Some(StatusFans34::FAN_3_WARNING));
+
+    let device = SimulatedDevice::new(1)
+        .with_register(STATUS_WORD, &[0x00, 0x04])
+        .with_register(STATUS_FANS_1_2, &[0x80])
+        .without_command(STATUS_FANS_3_4);
+    let mut bus = SimulatedBus::new().with_device(ADDRESS, device);
+    let reports = block_on(bus.read_fault_reports(ADDRESS, &[0], false)).unwrap();
+    assert_eq!(reports[0].fans_1_2, Some(StatusFans12::FAN_1_FAULT));
+    assert_eq!(reports[0].fans_3_4, None);
+}
+
+#[test]
+fn fault_reports_restore_page_on_error() {
+    let mut bus = Bus::new(&[
+        Transaction::write_read(ADDRESS, vec![PAGE], vec![0]),
+        Transaction::write(ADDRESS, vec![PAGE, 1]),
+        Transaction::write_read(ADDRESS, vec![STATUS_WORD], vec![0, 0])
+            .with_error(ErrorKind::Other),
+        Transaction::write(ADDRESS, vec![PAGE, 0]),
+    ]);
+    assert_eq!(
+        block_on(bus.read_fault_reports(ADDRESS, &[1, 2], false)),
+        Err(PmBusError::Bus(ErrorKind::Other))
+    );
+    bus.done();
+}
+
+#[test]
+fn clear_fault_reports() {
+    let mut device = SimulatedDevice::new(1);
+    device.set_register(0, STATUS_WORD, &[0x02, 0x00]);
+    device.set_register(0, STATUS_CML, &[0x40]);
+    let mut bus = SimulatedBus::new().with_device(ADDRESS, device);
+    let reports = block_on(bus.read_fault_reports(ADDRESS, &[0], true)).unwrap();
+    assert_eq!(reports[0].cml, Some(StatusCml::INVALID_DATA));
+    let reports = block_on(bus.read_fault_reports(ADDRESS, &[0], false)).unwrap();
+    assert_eq!(reports[0], FaultReport::new(0, StatusWord::empty()));
+}
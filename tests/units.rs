@@ -0,0 +1,66 @@
+mod common;
+
+use embedded_hal_mock::eh1::i2c::Transaction;
+use pmbus::commands::{PmBus, FREQUENCY_SWITCH, READ_FREQUENCY, READ_POUT, READ_TEMPERATURE_1};
+use pmbus::format::{Coefficients, NumericFormat};
+use pmbus::units::{Celsius, Hertz, Quantity, Watts};
+use pollster::block_on;
+
+use self::common::Bus;
+
+const ADDRESS: u8 = 0x40;
+
+#[test]
+fn display() {
+    assert_eq!(Watts(125.0).to_string(), "125 W");
+    assert_eq!(format!("{:.1}", Celsius(25.0)), "25.0 °C");
+    assert_eq!(Hertz::SYMBOL, "Hz");
+}
+
+#[test]
+fn numeric_linear11() {
+    let mut bus = Bus::new(&[
+        // 500·2^-2 W.
+        Transaction::write_read(ADDRESS, vec![READ_POUT], vec![0xF4, 0xF1]),
+    ]);
+    assert_eq!(
+        bus.numeric_format(ADDRESS, READ_POUT),
+        NumericFormat::Linear11
+    );
+    assert_eq!(block_on(bus.read_read_pout(ADDRESS)).unwrap(), Watts(125.0));
+    bus.done();
+}
+
+#[test]
+fn numeric_scaled() {
+    // 800·2^-1 kHz.
+    let mut bus = Bus::new(&[
+        Transaction::write(ADDRESS, vec![FREQUENCY_SWITCH, 0x20, 0xFB]),
+        Transaction::write_read(ADDRESS, vec![READ_FREQUENCY], vec![0x20, 0xFB]),
+    ]);
+    block_on(bus.send_frequency_switch(ADDRESS, Hertz(400_000.0))).unwrap();
+    assert_eq!(
+        block_on(bus.read_read_frequency(ADDRESS)).unwrap(),
+        Hertz(400_000.0)
+    );
+    bus.done();
+}
+
+#[test]
+fn numeric_direct() {
+    let mut bus = Bus::new(&[Transaction::write_read(
+        ADDRESS,
+        vec![READ_TEMPERATURE_1],
+        vec![0x05, 0x00],
+    )])
+    .with_fixed_coefficients(READ_TEMPERATURE_1, true, Coefficients::new(2, 0, -1));
+    assert_eq!(
+        bus.numeric_format(ADDRESS, READ_TEMPERATURE_1),
+        NumericFormat::Direct
+    );
+    assert_eq!(
+        block_on(bus.read_read_temperature_1(ADDRESS)).unwrap(),
+        Celsius(25.0)
+    );
+    bus.done();
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use embedded_hal_mock::eh1::i2c::Transaction;
+use pmbus::commands::{PmBus, READ_VOUT, VOUT_COMMAND, VOUT_MODE};
+use pmbus::error::{FormatError, PmBusError};
+use pmbus::format::{Vid, VidCode, VoutMode};
+use pmbus::units::Volts;
+use pollster::block_on;
+
+use self::common::Bus;
+
+const ADDRESS: u8 = 0x40;
+
+#[test]
+fn code_type_without_table() {
+    let mut bus = Bus::new(&[
+        Transaction::write_read(ADDRESS, vec![READ_VOUT], vec![0x97, 0x00]),
+        Transaction::write_read(ADDRESS, vec![VOUT_MODE], vec![0x23]),
+    ])
+    .with_vid_table(2, VidCode::Vr12);
+    assert_eq!(
+        block_on(bus.read_read_vout(ADDRESS)),
+        Err(PmBusError::Format(FormatError::Unsupported))
+    );
+    assert_eq!(
+        VoutMode::Vid { code: 2 }.decode(0x97),
+        Err(FormatError::Unsupported)
+    );
+    bus.done();
+}
+
+#[test]
+fn table_ends() {
+    for (table, first, last) in [
+        (VidCode::Vr11, 1.6, 0.5),
+        (VidCode::Vr12, 0.25, 1.52),
+        (VidCode::Vr12_5, 0.5, 3.04),
+        (VidCode::Vr13Step5mV, 0.25, 1.52),
+        (VidCode::Vr13Step10mV, 0.5, 3.04),
+        (VidCode::Vr14, 0.25, 1.52),
+        (VidCode::Imvp8, 0.25, 1.52),
+        (VidCode::Imvp9, 0.2, 2.74),
+    ] {
+        let (first_code, last_code) = if table == VidCode::Vr11 {
+            (0x02, 0xB2)
+        } else {
+            (0x01, 0xFF)
+        };
+        assert_eq!(Vid(first_code).to_f64(table), Ok(first), "{table:?}");
+        assert_eq!(Vid(last_code).to_f64(table), Ok(last), "{table:?}");
+    }
+}
+
+#[test]
+fn off_and_out_of_range_codes() {
+    for code in [0x00, 0x01, 0xFE, 0xFF] {
+        assert_eq!(Vid(code).to_f64(VidCode::Vr11), Ok(0.0));
+    }
+    assert_eq!(
+        Vid(0xB3).to_f64(VidCode::Vr11),
+        Err(FormatError::OutOfRange)
+    );
+    assert_eq!(Vid(0x00).to_f64(VidCode::Vr13Step10mV), Ok(0.0));
+    assert_eq!(
+        Vid(0x100).to_f64(VidCode::Vr12),
+        Err(FormatError::OutOfRange)
+    );
+
+    assert_eq!(Vid::from_f64(0.0, VidCode::Imvp9), Ok(Vid(0x00)));
+    assert_eq!(
+        Vid::from_f64(1.7, VidCode::Vr11),
+        Err(FormatError::OutOfRange)
+    );
+    assert_eq!(
+        Vid::from_f64(0.2, VidCode::Vr12),
+        Err(FormatError::OutOfRange)
+    );
+    assert_eq!(
+        Vid::from_f64(3.1, VidCode::Vr12_5),
+        Err(FormatError::OutOfRange)
+    );
+    assert_eq!(
+        Vid::from_f64(f64::NAN, VidCode::Vr12),
+        Err(FormatError::NotFinite)
+    );
+}
+
+#[test]
+fn encode_picks_nearest_code() {
+    assert_eq!(Vid::from_f64(1.0, VidCode::Vr12), Ok(Vid(0x97)));
+    assert_eq!(Vid::from_f64(1.0012, VidCode::Vr12), Ok(Vid(0x97)));
+    assert_eq!(Vid::from_f64(1.0, VidCode::Vr11), Ok(Vid(0x62)));
+    assert_eq!(Vid::from_f32(1.8, VidCode::Vr13Step10mV), Ok(Vid(0x83)));
+}
+
+#[test]
+fn round_trip_every_code() {
+    for table in VidCode::ALL {
+        for code in 0..=0xFF {
+            let vid = Vid(code);
+            match vid.to_f64(table) {
+                Ok(0.0) => assert!(table.is_off(code as u8)),
+                Ok(volts) => assert_eq!(Vid::from_f64(volts, table), Ok(vid), "{table:?}"),
+                Err(error) => assert_eq!(error, FormatError::OutOfRange),
+            }
+        }
+    }
+}
+
+#[test]
+fn vout_vid() {
+    // VR12.0.
+    let mut bus = Bus::new(&[
+        Transaction::write_read(ADDRESS, vec![VOUT_MODE], vec![0x22]),
+        Transaction::write(ADDRESS, vec![VOUT_COMMAND, 0x97, 0x00]),
+        Transaction::write_read(ADDRESS, vec![READ_VOUT], vec![0x97, 0x00]),
+        Transaction::write_read(ADDRESS, vec![VOUT_MODE], vec![0x22]),
+    ])
+    .with_vid_table(2, VidCode::Vr12);
+    assert_eq!(VoutMode::from(0x22), VoutMode::Vid { code: 2 });
+    block_on(bus.send_vout_command(ADDRESS, Volts(1.0))).unwrap();
+    assert_eq!(block_on(bus.read_read_vout(ADDRESS)).unwrap(), Volts(1.0));
+    bus.done();
+}
@@ -0,0 +1,207 @@
+mod common;
+
+use embedded_hal_mock::eh1::i2c::Transaction;
+use pmbus::commands::{
+    PmBus, PAGE, READ_VOUT, RESTORE_USER_ALL, VOUT_COMMAND, VOUT_MARGIN_HIGH, VOUT_MARGIN_LOW,
+    VOUT_MODE,
+};
+use pmbus::error::{FormatError, PmBusError};
+use pmbus::format::{Linear16, VoutMode};
+use pmbus::units::Volts;
+use pollster::block_on;
+
+use self::common::Bus;
+
+const ADDRESS: u8 = 0x40;
+
+/// ULINEAR16 with an exponent of -9.
+const MODE: u8 = 0x17;
+
+#[test]
+fn vout_mode_from_byte() {
+    assert_eq!(
+        VoutMode::from(MODE),
+        VoutMode::Linear {
+            exponent: -9,
+            relative: false,
+        }
+    );
+    assert_eq!(
+        VoutMode::from(0x0F),
+        VoutMode::Linear {
+            exponent: 15,
+            relative: false,
+        }
+    );
+    assert_eq!(
+        VoutMode::from(0x10),
+        VoutMode::Linear {
+            exponent: -16,
+            relative: false,
+        }
+    );
+    assert_eq!(VoutMode::from(0x21), VoutMode::Vid { code: 1 });
+    assert_eq!(VoutMode::from(0x40), VoutMode::Direct { relative: false });
+    for byte in [MODE, 0x0F, 0x10, 0x21, 0x40] {
+        assert_eq!(u8::from(VoutMode::from(byte)), byte);
+    }
+}
+
+#[test]
+fn linear16_codec() {
+    assert_eq!(Linear16(0x0266).to_f64(-9), 1.19921875);
+    assert_eq!(Linear16::from_f64(1.2, -9), Ok(Linear16(0x0266)));
+    assert_eq!(Linear16::from_f32(0.0, 4), Ok(Linear16(0)));
+    assert_eq!(Linear16::from_f64(-0.1, -9), Err(FormatError::OutOfRange));
+    assert_eq!(Linear16::from_f64(128.0, -9), Err(FormatError::OutOfRange));
+    assert_eq!(
+        Linear16::from_f64(f64::INFINITY, -9),
+        Err(FormatError::NotFinite)
+    );
+}
+
+#[test]
+fn vout_without_cache_reads_mode_every_time() {
+    let mut bus = Bus::new(&[
+        Transaction::write_read(ADDRESS, vec![VOUT_MODE], vec![MODE]),
+        Transaction::write(ADDRESS, vec![VOUT_COMMAND, 0x66, 0x02]),
+        Transaction::write_read(ADDRESS, vec![READ_VOUT], vec![0x00, 0x02]),
+        Transaction::write_read(ADDRESS, vec![VOUT_MODE], vec![MODE]),
+    ]);
+    block_on(bus.send_vout_command(ADDRESS, Volts(1.2))).unwrap();
+    assert_eq!(block_on(bus.read_read_vout(ADDRESS)).unwrap(), Volts(1.0));
+    bus.done();
+}
+
+#[test]
+fn vout_mode_cached_per_page() {
+    let mut bus = Bus::new(&[
+        Transaction::write_read(ADDRESS, vec![READ_VOUT], vec![0x00, 0x02]),
+        // The page is not known yet, so it is read before VOUT_MODE.
+        Transaction::write_read(ADDRESS, vec![PAGE], vec![0]),
+        Transaction::write_read(ADDRESS, vec![VOUT_MODE], vec![MODE]),
+        Transaction::write_read(ADDRESS, vec![READ_VOUT], vec![0x00, 0x04]),
+        // Page 1 has its own VOUT_MODE.
+        Transaction::write(ADDRESS, vec![PAGE, 1]),
+        Transaction::write_read(ADDRESS, vec![READ_VOUT], vec![0x00, 0x02]),
+        Transaction::write_read(ADDRESS, vec![VOUT_MODE], vec![0x18]),
+        // Returning to page 0 uses the cached mode.
+        Transaction::write(ADDRESS, vec![PAGE, 0]),
+        Transaction::write_read(ADDRESS, vec![READ_VOUT], vec![0x00, 0x02]),
+    ])
+    .with_cache();
+    assert_eq!(block_on(bus.read_read_vout(ADDRESS)).unwrap(), Volts(1.0));
+    assert_eq!(block_on(bus.read_read_vout(ADDRESS)).unwrap(), Volts(2.0));
+    block_on(bus.send_page(ADDRESS, 1)).unwrap();
+    assert_eq!(block_on(bus.read_read_vout(ADDRESS)).unwrap(), Volts(2.0));
+    block_on(bus.send_page(ADDRESS, 0)).unwrap();
+    assert_eq!(block_on(bus.read_read_vout(ADDRESS)).unwrap(), Volts(1.0));
+    bus.done();
+}
+
+#[test]
+fn vout_mode_write_replaces_cached_mode() {
+    let mut bus = Bus::new(&[
+        Transaction::write(ADDRESS, vec![PAGE, 0]),
+        Transaction::write(ADDRESS, vec![VOUT_MODE, 0x18]),
+        Transaction::write_read(ADDRESS, vec![READ_VOUT], vec![0x00, 0x02]),
+        // Restoring settings invalidates the cached mode.
+        Transaction::write(ADDRESS, vec![RESTORE_USER_ALL]),
+        Transaction::write_read(ADDRESS, vec![READ_VOUT], vec![0x00, 0x02]),
+        Transaction::write_read(ADDRESS, vec![VOUT_MODE], vec![MODE]),
+    ])
+    .with_cache();
+    block_on(bus.send_page(ADDRESS, 0)).unwrap();
+    block_on(bus.send_vout_mode(
+        ADDRESS,
+        VoutMode::Linear {
+            exponent: -8,
+            relative: false,
+        },
+    ))
+    .unwrap();
+    assert_eq!(
+        bus.cache().unwrap().vout_mode(ADDRESS, 0),
+        Some(VoutMode::Linear {
+            exponent: -8,
+            relative: false,
+        })
+    );
+    assert_eq!(block_on(bus.read_read_vout(ADDRESS)).unwrap(), Volts(2.0));
+    block_on(bus.write_restore_user_all(ADDRESS)).unwrap();
+    assert_eq!(block_on(bus.read_read_vout(ADDRESS)).unwrap(), Volts(1.0));
+    bus.done();
+}
+
+#[test]
+fn vout_unsupported_mode() {
+    let mut bus = Bus::new(&[
+        Transaction::write_read(ADDRESS, vec![READ_VOUT], vec![0x00, 0x02]),
+        Transaction::write_read(ADDRESS, vec![VOUT_MODE], vec![0x3F]),
+    ]);
+    assert_eq!(
+        block_on(bus.read_read_vout(ADDRESS)).unwrap_err(),
+        PmBusError::Format(FormatError::Unsupported)
+    );
+    bus.done();
+}
+
+#[test]
+fn vout_mode_flags() {
+    for (byte, mode) in [
+        (0x60, VoutMode::Half { relative: false }),
+        (0xE0, VoutMode::Half { relative: true }),
+        (0xC0, VoutMode::Direct { relative: true }),
+        (
+            0x97,
+            VoutMode::Linear {
+                exponent: -9,
+                relative: true,
+            },
+        ),
+        (0xA1, VoutMode::Reserved(0xA1)),
+    ] {
+        assert_eq!(VoutMode::from(byte), mode);
+        assert_eq!(u8::from(mode), byte);
+    }
+    assert!(VoutMode::from(0x97).is_relative());
+    assert_eq!(VoutMode::from(0x97).absolute(), VoutMode::from(MODE));
+}
+
+#[test]
+fn vout_half() {
+    let mut bus = Bus::new(&[
+        Transaction::write_read(ADDRESS, vec![VOUT_MODE], vec![0x60]),
+        Transaction::write(ADDRESS, vec![VOUT_COMMAND, 0xCD, 0x3C]),
+        Transaction::write_read(ADDRESS, vec![READ_VOUT], vec![0x00, 0x3E]),
+        Transaction::write_read(ADDRESS, vec![VOUT_MODE], vec![0x60]),
+    ]);
+    block_on(bus.send_vout_command(ADDRESS, Volts(1.2))).unwrap();
+    assert_eq!(block_on(bus.read_read_vout(ADDRESS)).unwrap(), Volts(1.5));
+    bus.done();
+}
+
+#[test]
+fn vout_relative() {
+    // Relative ULINEAR16 with an exponent of -9, and VOUT_COMMAND at 1 V.
+    let mut bus = Bus::new(&[
+        // +50‰ of VOUT_COMMAND.
+        Transaction::write_read(ADDRESS, vec![VOUT_MARGIN_HIGH], vec![0x00, 0x64]),
+        Transaction::write_read(ADDRESS, vec![VOUT_MODE], vec![0x97]),
+        Transaction::write_read(ADDRESS, vec![VOUT_COMMAND], vec![0x00, 0x02]),
+        // -62.5‰ of VOUT_COMMAND.
+        Transaction::write_read(ADDRESS, vec![VOUT_MODE], vec![0x97]),
+        Transaction::write_read(ADDRESS, vec![VOUT_COMMAND], vec![0x00, 0x02]),
+        Transaction::write(ADDRESS, vec![VOUT_MARGIN_LOW, 0x00, 0x83]),
+        // Other commands are absolute.
+        Transaction::write_read(ADDRESS, vec![READ_VOUT], vec![0x00, 0x83]),
+        Transaction::write_read(ADDRESS, vec![VOUT_MODE], vec![0x97]),
+    ]);
+    assert_eq!(
+        block_on(bus.read_vout_margin_high(ADDRESS)).unwrap(),
+        Volts(1.05)
+    );
+    block_on(bus.send_vout_margin_low(ADDRESS, Volts(0.9375))).unwrap();
+    assert_eq!(block_on(bus.read_read_vout(ADDRESS)).unwrap(), Volts(65.5));
+    bus.done();
+}